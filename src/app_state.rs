@@ -1,26 +1,35 @@
 use anyhow::{Context, Error, Result, bail};
-use crossterm::{QueueableCommand, cursor, terminal};
+use crossterm::{
+    QueueableCommand, cursor,
+    style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
+    terminal,
+};
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     env,
-    fs::{File, OpenOptions},
-    io::{Read, Seek, StdoutLock, Write},
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Read, StdoutLock, Write},
+    mem,
+    num::NonZeroUsize,
     path::{MAIN_SEPARATOR_STR, Path},
     process::{Command, Stdio},
+    str,
     sync::{
         atomic::{AtomicUsize, Ordering::Relaxed},
         mpsc,
     },
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    clear_terminal,
     cmd::CmdRunner,
+    diff::write_word_diff,
     embedded::EMBEDDED_FILES,
-    exercise::{Exercise, RunnableExercise},
+    exercise::{Exercise, OUTPUT_CAPACITY, RunnableExercise, hint_required_line},
+    history,
     info_file::ExerciseInfo,
-    term::{self, CheckProgressVisualizer},
+    term::{self, CheckProgressVisualizer, QueueStyle, clear_terminal},
 };
 
 const STATE_FILE_NAME: &str = ".rustlings-state.txt";
@@ -41,7 +50,34 @@ pub enum StateFileStatus {
     NotRead,
 }
 
-#[derive(Clone, Copy)]
+// The progress of an exercise which is saved in the state file.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct SavedProgress {
+    done: bool,
+    skipped: bool,
+    hint_viewed: bool,
+}
+
+impl SavedProgress {
+    #[inline]
+    fn of(exercise: &Exercise) -> Self {
+        Self {
+            done: exercise.done,
+            skipped: exercise.skipped,
+            hint_viewed: exercise.hint_viewed,
+        }
+    }
+}
+
+// The progress in the state file of exercises which don't exist.
+#[derive(Default)]
+struct UnknownProgress<'a> {
+    done: Vec<&'a [u8]>,
+    skipped: Vec<&'a [u8]>,
+    viewed_hints: Vec<&'a [u8]>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum CheckProgress {
     None,
     Checking,
@@ -55,35 +91,53 @@ pub struct AppState {
     // Caches the number of done exercises to avoid iterating over all exercises every time.
     n_done: u16,
     final_message: String,
-    state_file: File,
     // Preallocated buffer for reading and writing the state file.
     file_buf: Vec<u8>,
+    // The progress of each exercise in the state file after reading or writing it the last time.
+    // Exercises whose progress differs were changed by this process.
+    saved_progress: Vec<SavedProgress>,
     official_exercises: bool,
     cmd_runner: CmdRunner,
     // Running in VS Code.
     vs_code: bool,
+    // The order of checking all exercises. The first pending exercise in this order is the next one.
+    check_order: Vec<usize>,
+    // The duration of checking each exercise in the last run of checking all exercises.
+    check_durations: Vec<Duration>,
+    // The output of each pending exercise in the last run of checking all exercises.
+    // Empty for done exercises.
+    check_outputs: Vec<Vec<u8>>,
+    // An exercise can't be done before its hint was viewed.
+    teach_mode: bool,
 }
 
 impl AppState {
     pub fn new(
         exercise_infos: Vec<ExerciseInfo>,
         final_message: String,
+        cmd_runner: CmdRunner,
     ) -> Result<(Self, StateFileStatus)> {
-        let cmd_runner = CmdRunner::build()?;
-        let mut state_file = OpenOptions::new()
-            .create(true)
-            .read(true)
-            .write(true)
-            .truncate(false)
-            .open(STATE_FILE_NAME)
-            .with_context(|| {
-                format!("Failed to open or create the state file {STATE_FILE_NAME}")
-            })?;
-
         let dir_canonical_path = term::canonicalize("exercises");
+        let name_to_ind = exercise_infos
+            .iter()
+            .enumerate()
+            .map(|(ind, exercise_info)| (exercise_info.name.as_str(), ind))
+            .collect::<HashMap<_, _>>();
+        // Required exercises which are ignored don't block.
+        let requirements = exercise_infos
+            .iter()
+            .map(|exercise_info| {
+                exercise_info
+                    .requires
+                    .iter()
+                    .filter_map(|required| name_to_ind.get(required.as_str()).copied())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
         let mut exercises = exercise_infos
             .into_iter()
-            .map(|exercise_info| {
+            .zip(requirements)
+            .map(|(exercise_info, requires)| {
                 // Leaking to be able to borrow in the watch mode `Table`.
                 // Leaking is not a problem because the `AppState` instance lives until
                 // the end of the program.
@@ -91,6 +145,22 @@ impl AppState {
                 let name = exercise_info.name.leak();
                 let dir = exercise_info.dir.map(|dir| &*dir.leak());
                 let hint = exercise_info.hint.leak().trim_ascii();
+                let test_filter = exercise_info
+                    .test_filter
+                    .map(|test_filter| &*test_filter.leak());
+                let expected_output = exercise_info
+                    .expected_output
+                    .map(|expected_output| &*expected_output.leak());
+                let tags = exercise_info
+                    .tags
+                    .into_iter()
+                    .map(|tag| &*tag.leak())
+                    .collect();
+                let forbidden = exercise_info
+                    .forbidden
+                    .into_iter()
+                    .map(|pattern| &*pattern.leak())
+                    .collect();
 
                 let canonical_path = dir_canonical_path.as_deref().map(|dir_canonical_path| {
                     let mut canonical_path;
@@ -109,7 +179,12 @@ impl AppState {
 
                     canonical_path.push_str(MAIN_SEPARATOR_STR);
                     canonical_path.push_str(name);
-                    canonical_path.push_str(".rs");
+                    if exercise_info.multi_file {
+                        canonical_path.push_str(MAIN_SEPARATOR_STR);
+                        canonical_path.push_str("main.rs");
+                    } else {
+                        canonical_path.push_str(".rs");
+                    }
                     canonical_path
                 });
 
@@ -119,10 +194,24 @@ impl AppState {
                     path,
                     canonical_path,
                     test: exercise_info.test,
+                    test_filter,
                     strict_clippy: exercise_info.strict_clippy,
+                    expected_output,
+                    expected_exit_code: exercise_info.expected_exit_code,
+                    max_ns: exercise_info.max_ns,
+                    style_check: exercise_info.style_check,
+                    tags,
+                    forbidden,
+                    multi_file: exercise_info.multi_file,
+                    difficulty: exercise_info.difficulty,
+                    estimated_minutes: exercise_info.estimated_minutes,
+                    requires,
+                    env: exercise_info.env,
                     hint,
                     // Updated below.
+                    hint_viewed: false,
                     done: false,
+                    skipped: false,
                 }
             })
             .collect::<Vec<_>>();
@@ -131,35 +220,55 @@ impl AppState {
         let mut n_done = 0;
         let mut file_buf = Vec::with_capacity(2048);
         let state_file_status = 'block: {
-            if state_file.read_to_end(&mut file_buf).is_err() {
-                break 'block StateFileStatus::NotRead;
-            }
-
-            // See `Self::write` for more information about the file format.
-            let mut lines = file_buf.split(|c| *c == b'\n').skip(2);
-
-            let Some(current_exercise_name) = lines.next() else {
-                break 'block StateFileStatus::NotRead;
-            };
-
-            if current_exercise_name.is_empty() || lines.next().is_none() {
+            if let Err(e) =
+                File::open(STATE_FILE_NAME).and_then(|mut file| file.read_to_end(&mut file_buf))
+            {
+                if e.kind() != ErrorKind::NotFound {
+                    log::warn!("Failed to read the state file {STATE_FILE_NAME}: {e}");
+                }
                 break 'block StateFileStatus::NotRead;
             }
 
-            let mut done_exercises = HashSet::with_capacity(exercises.len());
+            let (current_exercise_name, done_exercises, skipped_exercises, viewed_hints) =
+                match parse_state_file(&file_buf) {
+                    ParsedStateFile::Valid {
+                        current_exercise_name,
+                        done_exercises,
+                        skipped_exercises,
+                        viewed_hints,
+                    } => (
+                        current_exercise_name,
+                        done_exercises,
+                        skipped_exercises,
+                        viewed_hints,
+                    ),
+                    ParsedStateFile::Invalid => {
+                        // An empty state file doesn't contain any progress to keep.
+                        if !file_buf.is_empty() {
+                            back_up_state_file("is corrupted")?;
+                        }
 
-            for done_exercise_name in lines {
-                if done_exercise_name.is_empty() {
-                    break;
-                }
-                done_exercises.insert(done_exercise_name);
-            }
+                        break 'block StateFileStatus::NotRead;
+                    }
+                    ParsedStateFile::UnknownVersion => {
+                        // Don't overwrite a state file which we don't understand.
+                        back_up_state_file(
+                            "has an unknown format version. \
+                         It is probably from a newer Rustlings version",
+                        )?;
+
+                        break 'block StateFileStatus::NotRead;
+                    }
+                };
 
             for (ind, exercise) in exercises.iter_mut().enumerate() {
                 if done_exercises.contains(exercise.name.as_bytes()) {
                     exercise.done = true;
                     n_done += 1;
+                } else if skipped_exercises.contains(exercise.name.as_bytes()) {
+                    exercise.skipped = true;
                 }
+                exercise.hint_viewed = viewed_hints.contains(exercise.name.as_bytes());
 
                 if exercise.name.as_bytes() == current_exercise_name {
                     current_exercise_ind = ind;
@@ -172,16 +281,22 @@ impl AppState {
         file_buf.clear();
         file_buf.extend_from_slice(STATE_FILE_HEADER);
 
+        let check_order = (0..exercises.len()).collect();
+        let saved_progress = exercises.iter().map(SavedProgress::of).collect();
         let slf = Self {
             current_exercise_ind,
             exercises,
             n_done,
             final_message,
-            state_file,
             file_buf,
+            saved_progress,
             official_exercises: !Path::new("info.toml").exists(),
             cmd_runner,
             vs_code: env::var_os("TERM_PROGRAM").is_some_and(|v| v == "vscode"),
+            check_order,
+            check_durations: Vec::new(),
+            check_outputs: Vec::new(),
+            teach_mode: false,
         };
 
         Ok((slf, state_file_status))
@@ -207,6 +322,15 @@ impl AppState {
         self.exercises.len() as u16 - self.n_done
     }
 
+    /// Percentage of done exercises. 0 if there are no exercises.
+    pub fn completion_percentage(&self) -> f64 {
+        if self.exercises.is_empty() {
+            return 0.0;
+        }
+
+        f64::from(self.n_done) * 100.0 / self.exercises.len() as f64
+    }
+
     #[inline]
     pub fn current_exercise(&self) -> &Exercise {
         &self.exercises[self.current_exercise_ind]
@@ -222,39 +346,209 @@ impl AppState {
         self.vs_code
     }
 
+    /// Require viewing the hint of an exercise before it can be done.
+    #[inline]
+    pub fn enable_teach_mode(&mut self) {
+        self.teach_mode = true;
+    }
+
+    /// Whether the exercise can't be done yet because its hint wasn't viewed in the teach mode.
+    #[inline]
+    pub fn hint_required(&self, exercise_ind: usize) -> bool {
+        self.teach_mode && !self.exercises[exercise_ind].hint_viewed
+    }
+
+    /// Record that the hint of an exercise was viewed. Only saved in the teach mode.
+    pub fn mark_hint_viewed(&mut self, exercise_ind: usize) -> Result<()> {
+        if !self.teach_mode {
+            return Ok(());
+        }
+
+        let exercise = self
+            .exercises
+            .get_mut(exercise_ind)
+            .context(BAD_INDEX_ERR)?;
+        if exercise.hint_viewed {
+            return Ok(());
+        }
+        exercise.hint_viewed = true;
+
+        self.write()
+    }
+
+    // Fill `file_buf` with the content of the state file.
+    // The progress of unknown exercises follows the progress of the known ones.
+    // See `STATE_FILE_HEADER` for the file's format.
+    fn fill_file_buf(&mut self, unknown: &UnknownProgress) {
+        let file_buf = &mut self.file_buf;
+        file_buf.truncate(STATE_FILE_HEADER.len());
+
+        file_buf.extend_from_slice(self.exercises[self.current_exercise_ind].name.as_bytes());
+        file_buf.push(b'\n');
+
+        for name in progress_names(&self.exercises, |exercise| exercise.done, &unknown.done) {
+            file_buf.push(b'\n');
+            file_buf.extend_from_slice(name);
+        }
+
+        let mut skipped_exercises = progress_names(
+            &self.exercises,
+            |exercise| exercise.skipped,
+            &unknown.skipped,
+        )
+        .peekable();
+        let mut viewed_hints = progress_names(
+            &self.exercises,
+            |exercise| exercise.hint_viewed,
+            &unknown.viewed_hints,
+        )
+        .peekable();
+        // The section of the skipped exercises is also needed as a separator if it is empty.
+        if skipped_exercises.peek().is_none() && viewed_hints.peek().is_none() {
+            return;
+        }
+
+        file_buf.extend_from_slice(b"\n\n");
+        push_lines(file_buf, skipped_exercises);
+
+        if viewed_hints.peek().is_some() {
+            file_buf.extend_from_slice(b"\n\n");
+            push_lines(file_buf, viewed_hints);
+        }
+    }
+
+    // Take over the progress which another Rustlings process saved in the state file
+    // since reading or writing it the last time. The changes of this process win.
+    // Returns the progress of the exercises in the state file which don't exist,
+    // for example because they are ignored. It is kept in the state file.
+    // Must be called while holding the lock of the state file.
+    fn merge_progress<'a>(&mut self, content: &'a [u8]) -> UnknownProgress<'a> {
+        let ParsedStateFile::Valid {
+            mut done_exercises,
+            mut skipped_exercises,
+            mut viewed_hints,
+            ..
+        } = parse_state_file(content)
+        else {
+            return UnknownProgress::default();
+        };
+
+        for (ind, exercise) in self.exercises.iter_mut().enumerate() {
+            let name = exercise.name.as_bytes();
+            let done = done_exercises.remove(name);
+            let skipped = skipped_exercises.remove(name);
+            let hint_viewed = viewed_hints.remove(name);
+
+            if self
+                .saved_progress
+                .get(ind)
+                .is_none_or(|saved_progress| SavedProgress::of(exercise) != *saved_progress)
+            {
+                continue;
+            }
+
+            if done && !exercise.done {
+                self.n_done += 1;
+            } else if !done && exercise.done {
+                self.n_done -= 1;
+            }
+            exercise.done = done;
+            exercise.skipped = !done && skipped;
+            exercise.hint_viewed = hint_viewed;
+        }
+
+        UnknownProgress {
+            done: sorted(done_exercises),
+            skipped: sorted(skipped_exercises),
+            viewed_hints: sorted(viewed_hints),
+        }
+    }
+
     // Write the state file.
-    // The file's format is very simple:
-    // - The first line is a comment.
-    // - The second line is an empty line.
-    // - The third line is the name of the current exercise. It must end with `\n` even if there
-    // are no done exercises.
-    // - The fourth line is an empty line.
-    // - All remaining lines are the names of done exercises.
     fn write(&mut self) -> Result<()> {
-        self.file_buf.truncate(STATE_FILE_HEADER.len());
+        // Another Rustlings process could write the state file at the same time.
+        // The lock is held while reading, merging and writing to not lose its changes.
+        let _lock = StateFileLock::acquire()?;
+
+        let content = fs::read(STATE_FILE_NAME).unwrap_or_default();
+        let unknown = self.merge_progress(&content);
+        self.fill_file_buf(&unknown);
+
+        // Write a temporary file and rename it to never leave a truncated state file behind,
+        // for example if Rustlings is killed while writing.
+        fs::write(STATE_FILE_TMP_NAME, &self.file_buf)
+            .with_context(|| format!("Failed to write the state file {STATE_FILE_TMP_NAME}"))?;
+        fs::rename(STATE_FILE_TMP_NAME, STATE_FILE_NAME).with_context(|| {
+            format!("Failed to rename the state file {STATE_FILE_TMP_NAME} to {STATE_FILE_NAME}")
+        })?;
 
-        self.file_buf
-            .extend_from_slice(self.current_exercise().name.as_bytes());
-        self.file_buf.push(b'\n');
+        self.saved_progress.clear();
+        self.saved_progress
+            .extend(self.exercises.iter().map(SavedProgress::of));
 
-        for exercise in &self.exercises {
-            if exercise.done {
-                self.file_buf.push(b'\n');
-                self.file_buf.extend_from_slice(exercise.name.as_bytes());
+        Ok(())
+    }
+
+    /// Export the progress in the format of the state file.
+    pub fn export_state(&mut self, path: &Path) -> Result<()> {
+        self.fill_file_buf(&UnknownProgress::default());
+
+        fs::write(path, &self.file_buf)
+            .with_context(|| format!("Failed to write the file {}", path.display()))
+    }
+
+    /// Import the progress from a file which was exported by `Self::export_state`.
+    /// The progress of exercises which don't exist in the file is reset to pending.
+    /// Returns the names of the done or skipped exercises in the file which don't exist (anymore).
+    pub fn import_state(&mut self, path: &Path) -> Result<Vec<String>> {
+        let content = fs::read(path)
+            .with_context(|| format!("Failed to read the file {}", path.display()))?;
+
+        let (current_exercise_name, mut done_exercises, mut skipped_exercises, viewed_hints) =
+            match parse_state_file(&content) {
+                ParsedStateFile::Valid {
+                    current_exercise_name,
+                    done_exercises,
+                    skipped_exercises,
+                    viewed_hints,
+                } => (
+                    current_exercise_name,
+                    done_exercises,
+                    skipped_exercises,
+                    viewed_hints,
+                ),
+                ParsedStateFile::Invalid => {
+                    bail!("The file {} isn't an exported state", path.display());
+                }
+                ParsedStateFile::UnknownVersion => bail!(
+                    "The file {} was exported by a newer Rustlings version",
+                    path.display(),
+                ),
+            };
+
+        for ind in 0..self.exercises.len() {
+            let name = self.exercises[ind].name.as_bytes();
+            let done = done_exercises.remove(name);
+            let skipped = skipped_exercises.remove(name);
+            if name == current_exercise_name {
+                self.current_exercise_ind = ind;
             }
+            self.set_status(ind, done)?;
+            let exercise = &mut self.exercises[ind];
+            exercise.skipped = skipped && !done;
+            exercise.hint_viewed = viewed_hints.contains(exercise.name.as_bytes());
         }
 
-        self.state_file
-            .rewind()
-            .with_context(|| format!("Failed to rewind the state file {STATE_FILE_NAME}"))?;
-        self.state_file
-            .set_len(0)
-            .with_context(|| format!("Failed to truncate the state file {STATE_FILE_NAME}"))?;
-        self.state_file
-            .write_all(&self.file_buf)
-            .with_context(|| format!("Failed to write the state file {STATE_FILE_NAME}"))?;
+        self.write()?;
 
-        Ok(())
+        let mut unknown_exercises = done_exercises
+            .into_iter()
+            .chain(skipped_exercises)
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect::<Vec<_>>();
+        unknown_exercises.sort_unstable();
+
+        Ok(unknown_exercises)
     }
 
     pub fn set_current_exercise_ind(&mut self, exercise_ind: usize) -> Result<()> {
@@ -271,28 +565,117 @@ impl AppState {
         self.write()
     }
 
-    pub fn set_current_exercise_by_name(&mut self, name: &str) -> Result<()> {
+    /// The names of the required exercises which aren't done yet.
+    pub fn missing_requirements(&self, exercise_ind: usize) -> Vec<&'static str> {
+        self.exercises[exercise_ind]
+            .requires
+            .iter()
+            .map(|&required_ind| &self.exercises[required_ind])
+            .filter(|required| !required.done)
+            .map(|required| required.name)
+            .collect()
+    }
+
+    /// Jump to an exercise if all of its required exercises are done.
+    pub fn jump_to_exercise(&mut self, exercise_ind: usize) -> Result<()> {
+        let missing_requirements = self.missing_requirements(exercise_ind);
+        if !missing_requirements.is_empty() {
+            bail!(
+                "The exercise `{}` requires the following exercises to be done first: {}",
+                self.exercises[exercise_ind].name,
+                missing_requirements.join(", "),
+            );
+        }
+
+        self.set_current_exercise_ind(exercise_ind)
+    }
+
+    pub fn exercise_ind_by_name(&self, name: &str) -> Result<usize> {
         // O(N) is fine since this method is used only once until the program exits.
         // Building a hashmap would have more overhead.
-        self.current_exercise_ind = self
-            .exercises
+        self.exercises
             .iter()
             .position(|exercise| exercise.name == name)
-            .with_context(|| format!("No exercise found for '{name}'!"))?;
+            .with_context(|| format!("No exercise found for '{name}'!"))
+    }
+
+    /// The indices of the exercises in a chapter directory like `09_strings`.
+    /// The chapter can also be given with the `exercises/` prefix.
+    pub fn chapter_exercise_inds(&self, chapter: &str) -> Result<Vec<usize>> {
+        let chapter = chapter.trim_end_matches('/');
+        let chapter = chapter.strip_prefix("exercises/").unwrap_or(chapter);
+
+        let exercise_inds: Vec<usize> = self
+            .exercises
+            .iter()
+            .enumerate()
+            .filter(|(_, exercise)| {
+                exercise
+                    .path
+                    .strip_prefix("exercises/")
+                    .and_then(|path| path.strip_prefix(chapter))
+                    .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .map(|(ind, _)| ind)
+            .collect();
+
+        if exercise_inds.is_empty() {
+            bail!("No exercises found in the chapter directory `exercises/{chapter}/`");
+        }
+
+        Ok(exercise_inds)
+    }
+
+    /// The indices of the exercises with the given tag.
+    pub fn tag_exercise_inds(&self, tag: &str) -> Result<Vec<usize>> {
+        let exercise_inds: Vec<usize> = self
+            .exercises
+            .iter()
+            .enumerate()
+            .filter(|(_, exercise)| exercise.has_tag(tag))
+            .map(|(ind, _)| ind)
+            .collect();
+
+        if exercise_inds.is_empty() {
+            bail!("No exercises found with the tag `{tag}`");
+        }
+
+        Ok(exercise_inds)
+    }
+
+    pub fn set_current_exercise_by_name(&mut self, name: &str) -> Result<()> {
+        self.current_exercise_ind = self.exercise_ind_by_name(name)?;
 
         self.write()
     }
 
+    // Set an exercise as done without saving and record it in the history if it wasn't done.
+    fn set_done(&mut self, exercise_ind: usize) -> Result<()> {
+        let was_done = self.exercises.get(exercise_ind).is_some_and(|e| e.done);
+        self.set_status(exercise_ind, true)?;
+        if !was_done {
+            history::record_done(self.exercises[exercise_ind].name);
+        }
+
+        Ok(())
+    }
+
     // Set the status of an exercise without saving. Returns `true` if the
     // status actually changed (and thus needs saving later).
+    // A done exercise isn't skipped anymore.
     pub fn set_status(&mut self, exercise_ind: usize, done: bool) -> Result<bool> {
         let exercise = self
             .exercises
             .get_mut(exercise_ind)
             .context(BAD_INDEX_ERR)?;
 
+        let was_skipped = exercise.skipped;
+        if done {
+            exercise.skipped = false;
+        }
+
         if exercise.done == done {
-            return Ok(false);
+            return Ok(was_skipped && done);
         }
 
         exercise.done = done;
@@ -316,6 +699,7 @@ impl AppState {
 
     // Official exercises: Dump the original file from the binary.
     // Third-party exercises: Reset the exercise file with `git stash`.
+    // All files of a multi-file exercise are reset.
     fn reset(&self, exercise_ind: usize, path: &str) -> Result<()> {
         if self.official_exercises {
             return EMBEDDED_FILES
@@ -323,6 +707,12 @@ impl AppState {
                 .with_context(|| format!("Failed to reset the exercise {path}"));
         }
 
+        let path = if self.exercises[exercise_ind].multi_file {
+            path.strip_suffix("/main.rs").unwrap_or(path)
+        } else {
+            path
+        };
+
         let output = Command::new("git")
             .arg("stash")
             .arg("push")
@@ -343,8 +733,81 @@ impl AppState {
         Ok(())
     }
 
+    // Official exercises: The original file embedded in the binary.
+    // Third-party exercises: The file's content in the last Git commit.
+    pub fn original_exercise_file(&self, exercise_ind: usize) -> Result<Vec<u8>> {
+        if self.official_exercises {
+            return Ok(EMBEDDED_FILES.exercise_file(exercise_ind).to_vec());
+        }
+
+        let path = self
+            .exercises
+            .get(exercise_ind)
+            .context(BAD_INDEX_ERR)?
+            .path;
+        let output = Command::new("git")
+            .arg("show")
+            .arg(format!("HEAD:./{path}"))
+            .stdin(Stdio::null())
+            .output()
+            .with_context(|| format!("Failed to run `git show HEAD:./{path}`"))?;
+
+        if !output.status.success() {
+            bail!(
+                "`git show HEAD:./{path}` didn't run successfully: {}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+        }
+
+        Ok(output.stdout)
+    }
+
+    /// Show the changes to an exercise file which resetting it would discard without resetting it.
+    pub fn write_reset_diff(&self, stdout: &mut StdoutLock, exercise_ind: usize) -> Result<()> {
+        let exercise = self.exercises.get(exercise_ind).context(BAD_INDEX_ERR)?;
+
+        stdout.queue_style(SetAttribute(Attribute::Underlined))?;
+        stdout.write_all(exercise.path.as_bytes())?;
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b"\n")?;
+
+        let current = match fs::read(exercise.path) {
+            Ok(current) => current,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                stdout.write_all(b"The missing file would be restored\n")?;
+                return Ok(());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| {
+                    format!("Failed to read the exercise file {}", exercise.path)
+                });
+            }
+        };
+        let original = self.original_exercise_file(exercise_ind)?;
+
+        if !write_word_diff(
+            stdout,
+            &String::from_utf8_lossy(&current),
+            &String::from_utf8_lossy(&original),
+        )? {
+            stdout.write_all(b"No changes compared to the original exercise file\n")?;
+        }
+
+        Ok(())
+    }
+
+    // Set an exercise to pending and forget that its hint was viewed.
+    fn reset_progress(&mut self, exercise_ind: usize) -> Result<()> {
+        let hint_viewed = mem::take(&mut self.exercises[exercise_ind].hint_viewed);
+        if self.set_status(exercise_ind, false)? || hint_viewed {
+            self.write()?;
+        }
+
+        Ok(())
+    }
+
     pub fn reset_current_exercise(&mut self) -> Result<&'static str> {
-        self.set_pending(self.current_exercise_ind)?;
+        self.reset_progress(self.current_exercise_ind)?;
         let exercise = self.current_exercise();
         self.reset(self.current_exercise_ind, exercise.path)?;
 
@@ -357,7 +820,7 @@ impl AppState {
             bail!(BAD_INDEX_ERR);
         }
 
-        self.set_pending(exercise_ind)?;
+        self.reset_progress(exercise_ind)?;
         let exercise = &self.exercises[exercise_ind];
         self.reset(exercise_ind, exercise.path)?;
 
@@ -365,7 +828,21 @@ impl AppState {
     }
 
     // Return the index of the next pending exercise or `None` if all exercises are done.
+    // Skipped exercises are ignored.
     fn next_pending_exercise_ind(&self) -> Option<usize> {
+        let is_pending = |exercise: &Exercise| !exercise.done && !exercise.skipped;
+        // Prefer the exercises whose required exercises are done.
+        self.next_exercise_ind_where(|exercise| {
+            is_pending(exercise)
+                && exercise
+                    .requires
+                    .iter()
+                    .all(|&required_ind| self.exercises[required_ind].done)
+        })
+        .or_else(|| self.next_exercise_ind_where(is_pending))
+    }
+
+    fn next_exercise_ind_where(&self, is_pending: impl Fn(&Exercise) -> bool) -> Option<usize> {
         let next_ind = self.current_exercise_ind + 1;
         self.exercises
             // If the exercise done isn't the last, search for pending exercises after it.
@@ -373,17 +850,60 @@ impl AppState {
             .and_then(|later_exercises| {
                 later_exercises
                     .iter()
-                    .position(|exercise| !exercise.done)
+                    .position(&is_pending)
                     .map(|ind| next_ind + ind)
             })
             // Search from the start.
             .or_else(|| {
                 self.exercises[..self.current_exercise_ind]
                     .iter()
-                    .position(|exercise| !exercise.done)
+                    .position(&is_pending)
             })
     }
 
+    /// Up to `n` pending exercises in the order of moving on from the current exercise.
+    /// Skipped exercises are ignored.
+    pub fn upcoming_exercises(&self, n: usize) -> impl Iterator<Item = &Exercise> {
+        self.exercises[self.current_exercise_ind + 1..]
+            .iter()
+            .chain(&self.exercises[..self.current_exercise_ind])
+            .filter(|exercise| !exercise.done && !exercise.skipped)
+            .take(n)
+    }
+
+    #[inline]
+    pub fn n_skipped(&self) -> usize {
+        self.exercises
+            .iter()
+            .filter(|exercise| exercise.skipped)
+            .count()
+    }
+
+    /// Skip a pending exercise for now. If it is the current exercise, move on to the next
+    /// pending exercise which isn't skipped. Returns `false` if there is no such exercise.
+    pub fn skip_exercise(&mut self, exercise_ind: usize) -> Result<bool> {
+        let exercise = self
+            .exercises
+            .get_mut(exercise_ind)
+            .context(BAD_INDEX_ERR)?;
+        if exercise.done {
+            bail!("The exercise {} is already done", exercise.name);
+        }
+        exercise.skipped = true;
+
+        let mut found_next = true;
+        if exercise_ind == self.current_exercise_ind {
+            match self.next_pending_exercise_ind() {
+                Some(ind) => self.current_exercise_ind = ind,
+                None => found_next = false,
+            }
+        }
+
+        self.write()?;
+
+        Ok(found_next)
+    }
+
     /// Official exercises: Dump the solution file from the binary and return its path.
     /// Third-party exercises: Check if a solution file exists and return its path in that case.
     pub fn current_solution_path(&self) -> Result<Option<String>> {
@@ -408,33 +928,139 @@ impl AppState {
         }
     }
 
-    fn check_all_exercises_impl(&mut self, stdout: &mut StdoutLock) -> Result<Option<usize>> {
-        let term_width = terminal::size()
-            .context("Failed to get the terminal size")?
-            .0;
-        let mut progress_visualizer = CheckProgressVisualizer::build(stdout, term_width)?;
+    // Official exercises: The solution file embedded in the binary.
+    // Third-party exercises: The solution file in the `solutions/` directory if it exists.
+    pub fn solution_file(&self, exercise_ind: usize) -> Result<Option<Vec<u8>>> {
+        if self.official_exercises {
+            return Ok(Some(EMBEDDED_FILES.solution_file(exercise_ind).to_vec()));
+        }
+
+        let sol_path = self.exercises[exercise_ind].sol_path();
+        match fs::read(&sol_path) {
+            Ok(solution) => Ok(Some(solution)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => {
+                Err(Error::from(e).context(format!("Failed to read the solution file {sol_path}")))
+            }
+        }
+    }
+
+    /// The duration of checking each exercise in the last run of checking all exercises.
+    /// Empty if all exercises weren't checked yet.
+    #[inline]
+    pub fn check_durations(&self) -> &[Duration] {
+        &self.check_durations
+    }
+
+    /// The output of a pending exercise in the last run of checking all exercises.
+    /// Empty if the exercise is done or wasn't checked.
+    #[inline]
+    pub fn check_output(&self, exercise_ind: usize) -> &[u8] {
+        self.check_outputs
+            .get(exercise_ind)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    // Check an exercise and capture its output.
+    // A working exercise is still pending if its hint wasn't viewed in the teach mode.
+    // Returns whether the exercise is done.
+    fn check_exercise_output(&self, exercise_ind: usize, output: &mut Vec<u8>) -> Result<bool> {
+        let exercise = &self.exercises[exercise_ind];
+        let success = exercise.run_exercise(Some(output), &self.cmd_runner)?;
+        if success && self.hint_required(exercise_ind) {
+            hint_required_line(output, exercise.name)?;
+            return Ok(false);
+        }
+
+        Ok(success)
+    }
+
+    /// Check all exercises in a random order which is reproducible with the same seed.
+    pub fn shuffle_check_order(&mut self, seed: u64) {
+        shuffle(&mut self.check_order, seed);
+    }
+
+    /// Pick a random exercise which is neither done nor skipped and whose required exercises are done.
+    /// The same seed picks the same exercise as long as the progress doesn't change.
+    pub fn random_pending_exercise_ind(&self, seed: u64) -> Option<usize> {
+        let pending_exercise_inds: Vec<usize> = self
+            .exercises
+            .iter()
+            .enumerate()
+            .filter(|(ind, exercise)| {
+                !exercise.done && !exercise.skipped && self.missing_requirements(*ind).is_empty()
+            })
+            .map(|(ind, _)| ind)
+            .collect();
+        if pending_exercise_inds.is_empty() {
+            return None;
+        }
+
+        let mut state = seed;
+        let ind = next_random(&mut state) % pending_exercise_inds.len() as u64;
+        Some(pending_exercise_inds[ind as usize])
+    }
+
+    /// Indices of the exercises which are checked by `check_all_exercises` in their order.
+    #[inline]
+    pub fn check_order(&self) -> &[usize] {
+        &self.check_order
+    }
+
+    /// Only check the exercises with these indices instead of all exercises.
+    pub fn restrict_check_order(&mut self, exercise_inds: &[usize]) {
+        self.check_order
+            .retain(|exercise_ind| exercise_inds.contains(exercise_ind));
+    }
+
+    fn check_all_exercises_impl(
+        &mut self,
+        stdout: &mut StdoutLock,
+        quiet: bool,
+        max_failures: Option<NonZeroUsize>,
+    ) -> Result<Option<usize>> {
+        let mut progress_visualizer = if quiet {
+            CheckProgressVisualizer::hidden(stdout)
+        } else {
+            let term_width = if term::escape_sequences_supported(stdout) {
+                terminal::size()
+                    .context("Failed to get the terminal size")?
+                    .0
+            } else {
+                0
+            };
+            CheckProgressVisualizer::build(stdout, term_width, self.check_order.len())?
+        };
 
         let next_exercise_ind = AtomicUsize::new(0);
         let mut progresses = vec![CheckProgress::None; self.exercises.len()];
+        let mut check_durations = vec![Duration::ZERO; self.exercises.len()];
+        let mut check_outputs = vec![Vec::new(); self.exercises.len()];
+        let mut n_failures = 0;
+        let max_failures_reached =
+            |n_failures: usize| max_failures.is_some_and(|max| n_failures >= max.get());
 
         thread::scope(|s| {
             let (exercise_progress_sender, exercise_progress_receiver) = mpsc::channel();
             let n_threads = thread::available_parallelism()
                 .map_or(DEFAULT_CHECK_PARALLELISM, |count| count.get());
 
+            let mut handles = Vec::with_capacity(n_threads);
             for _ in 0..n_threads {
                 let exercise_progress_sender = exercise_progress_sender.clone();
                 let next_exercise_ind = &next_exercise_ind;
                 let slf = &self;
-                thread::Builder::new()
+                let handle = thread::Builder::new()
                     .spawn_scoped(s, move || {
-                        loop {
-                            let exercise_ind = next_exercise_ind.fetch_add(1, Relaxed);
-                            let Some(exercise) = slf.exercises.get(exercise_ind) else {
-                                // No more exercises.
-                                break;
-                            };
-
+                        // Exercise indices with the durations of checking them and the
+                        // outputs of pending exercises.
+                        let mut results = Vec::new();
+                        let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+
+                        // Until there are no more exercises.
+                        while let Some(&exercise_ind) =
+                            slf.check_order.get(next_exercise_ind.fetch_add(1, Relaxed))
+                        {
                             if exercise_progress_sender
                                 .send((exercise_ind, CheckProgress::Checking))
                                 .is_err()
@@ -442,12 +1068,21 @@ impl AppState {
                                 break;
                             };
 
-                            let success = exercise.run_exercise(None, &slf.cmd_runner);
+                            let start = Instant::now();
+                            let success = slf.check_exercise_output(exercise_ind, &mut output);
+                            let duration = start.elapsed();
                             let progress = match success {
                                 Ok(true) => CheckProgress::Done,
                                 Ok(false) => CheckProgress::Pending,
                                 Err(_) => CheckProgress::None,
                             };
+                            // Only keep the output of pending exercises to show it later.
+                            let output = if progress == CheckProgress::Pending {
+                                mem::replace(&mut output, Vec::with_capacity(OUTPUT_CAPACITY))
+                            } else {
+                                Vec::new()
+                            };
+                            results.push((exercise_ind, duration, output));
 
                             if exercise_progress_sender
                                 .send((exercise_ind, progress))
@@ -456,65 +1091,119 @@ impl AppState {
                                 break;
                             }
                         }
+
+                        results
                     })
                     .context("Failed to spawn a thread to check all exercises")?;
+                handles.push(handle);
             }
 
             // Drop this sender to detect when the last thread is done.
             drop(exercise_progress_sender);
 
             while let Ok((exercise_ind, progress)) = exercise_progress_receiver.recv() {
+                if progress == CheckProgress::Pending {
+                    n_failures += 1;
+                    if max_failures_reached(n_failures) {
+                        // Don't start checking further exercises.
+                        // The ones which are already being checked are finished.
+                        next_exercise_ind.fetch_max(self.check_order.len(), Relaxed);
+                    }
+                }
+
                 progresses[exercise_ind] = progress;
                 progress_visualizer.update(&progresses)?;
             }
 
+            for handle in handles {
+                // A thread can only panic if the program aborts.
+                for (exercise_ind, duration, output) in handle.join().unwrap_or_default() {
+                    check_durations[exercise_ind] = duration;
+                    check_outputs[exercise_ind] = output;
+                }
+            }
+
             Ok::<_, Error>(())
         })?;
+        self.check_durations = check_durations;
+        self.check_outputs = check_outputs;
 
         let mut first_pending_exercise_ind = None;
-        for exercise_ind in 0..progresses.len() {
+        for order_ind in 0..self.check_order.len() {
+            let exercise_ind = self.check_order[order_ind];
             match progresses[exercise_ind] {
-                CheckProgress::Done => {
-                    self.set_status(exercise_ind, true)?;
-                }
+                CheckProgress::Done => self.set_done(exercise_ind)?,
                 CheckProgress::Pending => {
                     self.set_status(exercise_ind, false)?;
-                    if first_pending_exercise_ind.is_none() {
+                    if first_pending_exercise_ind.is_none() && !self.exercises[exercise_ind].skipped
+                    {
                         first_pending_exercise_ind = Some(exercise_ind);
                     }
                 }
                 CheckProgress::None | CheckProgress::Checking => {
+                    if max_failures_reached(n_failures) {
+                        // Not checked because of the maximum number of failures.
+                        continue;
+                    }
+
                     // If we got an error while checking all exercises in parallel,
                     // it could be because we exceeded the limit of open file descriptors.
                     // Therefore, try running exercises with errors sequentially.
                     progresses[exercise_ind] = CheckProgress::Checking;
                     progress_visualizer.update(&progresses)?;
 
-                    let exercise = &self.exercises[exercise_ind];
-                    let success = exercise.run_exercise(None, &self.cmd_runner)?;
+                    let start = Instant::now();
+                    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+                    let success = self.check_exercise_output(exercise_ind, &mut output)?;
+                    self.check_durations[exercise_ind] = start.elapsed();
                     if success {
                         progresses[exercise_ind] = CheckProgress::Done;
+                        self.set_done(exercise_ind)?;
                     } else {
                         progresses[exercise_ind] = CheckProgress::Pending;
-                        if first_pending_exercise_ind.is_none() {
+                        n_failures += 1;
+                        if first_pending_exercise_ind.is_none()
+                            && !self.exercises[exercise_ind].skipped
+                        {
                             first_pending_exercise_ind = Some(exercise_ind);
                         }
+                        self.check_outputs[exercise_ind] = output;
+                        self.set_status(exercise_ind, false)?;
                     }
-                    self.set_status(exercise_ind, success)?;
                     progress_visualizer.update(&progresses)?;
                 }
             }
         }
 
+        // Only keep the exercises which were actually checked.
+        self.check_order.retain(|&exercise_ind| {
+            matches!(
+                progresses[exercise_ind],
+                CheckProgress::Done | CheckProgress::Pending,
+            )
+        });
+
         self.write()?;
 
         Ok(first_pending_exercise_ind)
     }
 
     // Return the exercise index of the first pending exercise found.
-    pub fn check_all_exercises(&mut self, stdout: &mut StdoutLock) -> Result<Option<usize>> {
+    // Don't print the progress if `quiet` is true.
+    // Stop checking further exercises once `max_failures` exercises are pending. Afterwards, the
+    // check order only contains the exercises which were checked.
+    pub fn check_all_exercises(
+        &mut self,
+        stdout: &mut StdoutLock,
+        quiet: bool,
+        max_failures: Option<NonZeroUsize>,
+    ) -> Result<Option<usize>> {
+        if quiet || !term::escape_sequences_supported(stdout) {
+            return self.check_all_exercises_impl(stdout, quiet, max_failures);
+        }
+
         stdout.queue(cursor::Hide)?;
-        let res = self.check_all_exercises_impl(stdout);
+        let res = self.check_all_exercises_impl(stdout, false, max_failures);
         stdout.queue(cursor::Show)?;
 
         res
@@ -531,6 +1220,7 @@ impl AppState {
         if !exercise.done {
             exercise.done = true;
             self.n_done += 1;
+            history::record_done(exercise.name);
         }
 
         if let Some(ind) = self.next_pending_exercise_ind() {
@@ -544,7 +1234,7 @@ impl AppState {
             stdout.write_all(b"\n")?;
         }
 
-        if let Some(first_pending_exercise_ind) = self.check_all_exercises(stdout)? {
+        if let Some(first_pending_exercise_ind) = self.check_all_exercises(stdout, false, None)? {
             self.set_current_exercise_ind(first_pending_exercise_ind)?;
 
             return Ok(ExercisesProgress::NewPending);
@@ -558,6 +1248,10 @@ impl AppState {
     pub fn render_final_message(&self, stdout: &mut StdoutLock) -> Result<()> {
         clear_terminal(stdout)?;
         stdout.write_all(FENISH_LINE.as_bytes())?;
+        stdout.queue_style(SetForegroundColor(Color::Red))?;
+        stdout.write_all(FERRIS.as_bytes())?;
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b"\n\n")?;
 
         let final_message = self.final_message.trim_ascii();
         if !final_message.is_empty() {
@@ -569,13 +1263,240 @@ impl AppState {
     }
 }
 
+// The SplitMix64 generator.
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// Fisher–Yates shuffle.
+fn shuffle<T>(slice: &mut [T], seed: u64) {
+    let mut state = seed;
+    for ind in (1..slice.len()).rev() {
+        let other_ind = (next_random(&mut state) % (ind as u64 + 1)) as usize;
+        slice.swap(ind, other_ind);
+    }
+}
+
 const BAD_INDEX_ERR: &str = "The current exercise index is higher than the number of exercises";
-const STATE_FILE_HEADER: &[u8] = b"DON'T EDIT THIS FILE!\n\n";
+// Increment on changes of the state file format and migrate older versions in `parse_state_file`.
+// A macro to use the version in `STATE_FILE_HEADER`.
+macro_rules! state_file_version {
+    () => {
+        4
+    };
+}
+const STATE_FILE_VERSION: u8 = state_file_version!();
+// The state file starts with this header and has the following format:
+// - The first line is a comment.
+// - The second line is the format version.
+// - The third line is the name of the current exercise. It must end with `\n` even if there
+// are no done exercises.
+// - The fourth line is an empty line.
+// - The following lines are the names of done exercises.
+// - If there are skipped exercises or viewed hints, an empty line follows and the next lines
+// are the names of skipped exercises until an empty line.
+// - If there are viewed hints, the remaining lines are the names of the exercises whose hints
+// were viewed in the teach mode.
+const STATE_FILE_HEADER: &[u8] = concat!(
+    "DON'T EDIT THIS FILE!\nversion: ",
+    state_file_version!(),
+    "\n",
+)
+.as_bytes();
+const STATE_FILE_BACKUP_NAME: &str = ".rustlings-state.txt.bak";
+const STATE_FILE_TMP_NAME: &str = ".rustlings-state.txt.tmp";
+const STATE_FILE_LOCK_NAME: &str = ".rustlings-state.txt.lock";
+// The lock is only held for the few milliseconds of reading and writing the state file.
+// An older lock file was left behind by a crashed process.
+const STALE_LOCK_AGE: Duration = Duration::from_secs(2);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Held while reading and writing the state file. The lock file is removed on drop.
+// A lock file is used instead of OS file locks to work the same way on all platforms.
+struct StateFileLock;
+
+impl StateFileLock {
+    // Wait until no other process holds the lock.
+    fn acquire() -> Result<Self> {
+        let start = Instant::now();
+
+        loop {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(STATE_FILE_LOCK_NAME)
+            {
+                Ok(_) => return Ok(Self),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => (),
+                Err(e) => {
+                    return Err(Error::from(e).context(format!(
+                        "Failed to create the lock file {STATE_FILE_LOCK_NAME}"
+                    )));
+                }
+            }
+
+            let stale = fs::metadata(STATE_FILE_LOCK_NAME)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age > STALE_LOCK_AGE));
+            if stale {
+                log::debug!("Removing the stale lock file {STATE_FILE_LOCK_NAME}");
+                // Another process could have removed it already.
+                let _ = fs::remove_file(STATE_FILE_LOCK_NAME);
+                continue;
+            }
+
+            if start.elapsed() > LOCK_TIMEOUT {
+                bail!(
+                    "Another Rustlings process is writing the state file {STATE_FILE_NAME}. \
+                     If no other Rustlings process is running, remove the file {STATE_FILE_LOCK_NAME}",
+                );
+            }
+
+            thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+}
+
+impl Drop for StateFileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(STATE_FILE_LOCK_NAME);
+    }
+}
+
+// Keep the progress in a state file which can't be read before starting with a fresh state.
+fn back_up_state_file(problem: &str) -> Result<()> {
+    fs::copy(STATE_FILE_NAME, STATE_FILE_BACKUP_NAME).with_context(|| {
+        format!("Failed to back up the state file {STATE_FILE_NAME} to {STATE_FILE_BACKUP_NAME}")
+    })?;
+
+    log::warn!(
+        "The state file {STATE_FILE_NAME} {problem}. \
+         It was backed up to {STATE_FILE_BACKUP_NAME}. Starting with a fresh state",
+    );
+
+    Ok(())
+}
+
+// The names of the exercises matching the filter followed by the unknown names.
+fn progress_names<'a>(
+    exercises: &'a [Exercise],
+    filter: fn(&Exercise) -> bool,
+    unknown: &'a [&'a [u8]],
+) -> impl Iterator<Item = &'a [u8]> {
+    exercises
+        .iter()
+        .filter(move |exercise| filter(exercise))
+        .map(|exercise| exercise.name.as_bytes())
+        .chain(unknown.iter().copied())
+}
+
+// Push the lines separated by `\n` without a trailing `\n`.
+fn push_lines<'a>(buf: &mut Vec<u8>, mut lines: impl Iterator<Item = &'a [u8]>) {
+    let Some(first_line) = lines.next() else {
+        return;
+    };
+    buf.extend_from_slice(first_line);
+    for line in lines {
+        buf.push(b'\n');
+        buf.extend_from_slice(line);
+    }
+}
+
+// Sorted to write the state file deterministically.
+fn sorted(names: HashSet<&[u8]>) -> Vec<&[u8]> {
+    let mut names = names.into_iter().collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+// Parse the second line of the state file. Version 1 didn't have a version line.
+fn state_file_version(line: &[u8]) -> Option<u8> {
+    if line.is_empty() {
+        return Some(1);
+    }
+
+    str::from_utf8(line.strip_prefix(b"version: ")?)
+        .ok()?
+        .parse()
+        .ok()
+}
+
+enum ParsedStateFile<'a> {
+    Valid {
+        current_exercise_name: &'a [u8],
+        done_exercises: HashSet<&'a [u8]>,
+        skipped_exercises: HashSet<&'a [u8]>,
+        viewed_hints: HashSet<&'a [u8]>,
+    },
+    /// Empty, incomplete or edited.
+    Invalid,
+    /// Probably written by a newer Rustlings version.
+    UnknownVersion,
+}
+
+// See `STATE_FILE_HEADER` for the file's format.
+fn parse_state_file(file_buf: &[u8]) -> ParsedStateFile<'_> {
+    let mut lines = file_buf.split(|c| *c == b'\n').skip(1);
+
+    let Some(version_line) = lines.next() else {
+        return ParsedStateFile::Invalid;
+    };
+
+    let version = match state_file_version(version_line) {
+        // Version 1 only differs in the missing version line.
+        // Version 2 only differs in the missing skipped exercises.
+        // Version 3 only differs in the missing viewed hints.
+        // They are migrated by writing the state file in the new format.
+        Some(version) if version <= STATE_FILE_VERSION => version,
+        _ => return ParsedStateFile::UnknownVersion,
+    };
+
+    let Some(current_exercise_name) = lines.next() else {
+        return ParsedStateFile::Invalid;
+    };
+
+    if current_exercise_name.is_empty() || lines.next().is_none() {
+        return ParsedStateFile::Invalid;
+    }
+
+    let done_exercises = lines
+        .by_ref()
+        .take_while(|done_exercise_name| !done_exercise_name.is_empty())
+        .collect();
+    // Versions before 3 don't have skipped exercises.
+    // Versions before 4 don't have viewed hints. All remaining lines are skipped exercises.
+    let skipped_exercises = if version < 4 {
+        lines
+            .by_ref()
+            .filter(|skipped_exercise_name| !skipped_exercise_name.is_empty())
+            .collect()
+    } else {
+        lines
+            .by_ref()
+            .take_while(|skipped_exercise_name| !skipped_exercise_name.is_empty())
+            .collect()
+    };
+    let viewed_hints = lines.filter(|name| !name.is_empty()).collect();
+
+    ParsedStateFile::Valid {
+        current_exercise_name,
+        done_exercises,
+        skipped_exercises,
+        viewed_hints,
+    }
+}
+
 const FENISH_LINE: &str = "+----------------------------------------------------+
 |          You made it to the Fe-nish line!          |
 +--------------------------  ------------------------+
-                           \\/\x1b[31m
-     ▒▒          ▒▒▒▒▒▒▒▒      ▒▒▒▒▒▒▒▒          ▒▒
+                           \\/
+";
+const FERRIS: &str = "     ▒▒          ▒▒▒▒▒▒▒▒      ▒▒▒▒▒▒▒▒          ▒▒
    ▒▒▒▒  ▒▒    ▒▒        ▒▒  ▒▒        ▒▒    ▒▒  ▒▒▒▒
    ▒▒▒▒  ▒▒  ▒▒            ▒▒            ▒▒  ▒▒  ▒▒▒▒
  ░░▒▒▒▒░░▒▒  ▒▒            ▒▒            ▒▒  ▒▒░░▒▒▒▒
@@ -589,13 +1510,12 @@ const FENISH_LINE: &str = "+----------------------------------------------------
          ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒
        ▒▒    ▒▒    ▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒▒    ▒▒    ▒▒
        ▒▒  ▒▒    ▒▒                  ▒▒    ▒▒  ▒▒
-           ▒▒  ▒▒                      ▒▒  ▒▒\x1b[0m
-
-";
+           ▒▒  ▒▒                      ▒▒  ▒▒";
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
 
     fn dummy_exercise() -> Exercise {
         Exercise {
@@ -604,25 +1524,50 @@ mod tests {
             path: "exercises/0.rs",
             canonical_path: None,
             test: false,
+            test_filter: None,
             strict_clippy: false,
+            expected_output: None,
+            expected_exit_code: 0,
+            max_ns: None,
+            style_check: false,
+            tags: Vec::new(),
+            forbidden: Vec::new(),
+            multi_file: false,
+            difficulty: None,
+            estimated_minutes: None,
+            requires: Vec::new(),
+            env: BTreeMap::new(),
             hint: "",
+            hint_viewed: false,
             done: false,
+            skipped: false,
         }
     }
 
-    #[test]
-    fn next_pending_exercise() {
-        let mut app_state = AppState {
+    // A state with these exercises in their order. The first exercise is the current one.
+    fn dummy_app_state(exercises: Vec<Exercise>) -> AppState {
+        let check_order = (0..exercises.len()).collect();
+        AppState {
             current_exercise_ind: 0,
-            exercises: vec![dummy_exercise(), dummy_exercise(), dummy_exercise()],
+            exercises,
             n_done: 0,
             final_message: String::new(),
-            state_file: tempfile::tempfile().unwrap(),
             file_buf: Vec::new(),
+            saved_progress: Vec::new(),
             official_exercises: true,
             cmd_runner: CmdRunner::build().unwrap(),
             vs_code: false,
-        };
+            check_order,
+            check_durations: Vec::new(),
+            check_outputs: Vec::new(),
+            teach_mode: false,
+        }
+    }
+
+    #[test]
+    fn next_pending_exercise() {
+        let mut app_state =
+            dummy_app_state(vec![dummy_exercise(), dummy_exercise(), dummy_exercise()]);
 
         let mut assert = |done: [bool; 3], expected: [Option<usize>; 3]| {
             for (exercise, done) in app_state.exercises.iter_mut().zip(done) {
@@ -646,5 +1591,243 @@ mod tests {
         assert([true, false, false], [Some(1), Some(2), Some(1)]);
         assert([false, true, false], [Some(2), Some(2), Some(0)]);
         assert([false, false, true], [Some(1), Some(0), Some(0)]);
+
+        // Skipped exercises are ignored.
+        for exercise in &mut app_state.exercises {
+            exercise.done = false;
+        }
+        app_state.exercises[1].skipped = true;
+        app_state.current_exercise_ind = 0;
+        assert_eq!(app_state.next_pending_exercise_ind(), Some(2));
+        app_state.exercises[2].skipped = true;
+        assert_eq!(app_state.next_pending_exercise_ind(), None);
+    }
+
+    #[test]
+    fn upcoming_exercises() {
+        let mut app_state = dummy_app_state(
+            ["0", "1", "2", "3", "4", "5"]
+                .into_iter()
+                .map(|name| Exercise {
+                    name,
+                    ..dummy_exercise()
+                })
+                .collect(),
+        );
+        app_state.current_exercise_ind = 2;
+        let upcoming = |app_state: &AppState, n| {
+            app_state
+                .upcoming_exercises(n)
+                .map(|exercise| exercise.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(upcoming(&app_state, 3), ["3", "4", "5"]);
+        assert_eq!(upcoming(&app_state, 10), ["3", "4", "5", "0", "1"]);
+
+        app_state.exercises[3].done = true;
+        app_state.exercises[5].skipped = true;
+        assert_eq!(upcoming(&app_state, 3), ["4", "0", "1"]);
+
+        for exercise in &mut app_state.exercises {
+            exercise.done = true;
+        }
+        assert_eq!(upcoming(&app_state, 3), [""; 0]);
+    }
+
+    #[test]
+    fn random_pending_exercise() {
+        let mut app_state = dummy_app_state((0..4).map(|_| dummy_exercise()).collect());
+
+        let picked = app_state.random_pending_exercise_ind(42);
+        assert_eq!(picked, app_state.random_pending_exercise_ind(42));
+        assert!((0..100).all(|seed| app_state.random_pending_exercise_ind(seed).is_some()));
+
+        app_state.exercises[0].done = true;
+        app_state.exercises[1].skipped = true;
+        app_state.exercises[3].done = true;
+        assert!((0..100).all(|seed| app_state.random_pending_exercise_ind(seed) == Some(2)));
+
+        app_state.exercises[2].done = true;
+        assert_eq!(app_state.random_pending_exercise_ind(42), None);
+    }
+
+    #[test]
+    fn hint_required() {
+        let mut app_state = dummy_app_state(vec![dummy_exercise(), dummy_exercise()]);
+
+        // Without the teach mode, hints are optional.
+        assert!(!app_state.hint_required(0));
+
+        app_state.teach_mode = true;
+        app_state.exercises[1].hint_viewed = true;
+        assert!(app_state.hint_required(0));
+        assert!(!app_state.hint_required(1));
+    }
+
+    #[test]
+    fn shuffle_reproducible() {
+        let shuffled = |seed| {
+            let mut order = (0..100).collect::<Vec<_>>();
+            shuffle(&mut order, seed);
+            order
+        };
+
+        let order = shuffled(42);
+        assert_eq!(order, shuffled(42));
+        assert_ne!(order, shuffled(43));
+        assert_ne!(order, (0..100).collect::<Vec<_>>());
+
+        let mut sorted = order;
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn state_file_versions() {
+        assert_eq!(state_file_version(b""), Some(1));
+        assert_eq!(state_file_version(b"version: 2"), Some(2));
+        assert_eq!(state_file_version(b"version: 3"), Some(3));
+        assert_eq!(state_file_version(b"version: 4"), Some(4));
+        assert_eq!(state_file_version(b"version: x"), None);
+        assert_eq!(state_file_version(b"intro"), None);
+
+        let header_version = STATE_FILE_HEADER
+            .split(|c| *c == b'\n')
+            .nth(1)
+            .and_then(state_file_version);
+        assert_eq!(header_version, Some(STATE_FILE_VERSION));
+    }
+
+    #[test]
+    fn merge_progress() {
+        let mut app_state = dummy_app_state(
+            ["a", "b", "c"]
+                .map(|name| Exercise {
+                    name,
+                    ..dummy_exercise()
+                })
+                .into(),
+        );
+        app_state.saved_progress = app_state.exercises.iter().map(SavedProgress::of).collect();
+        // Changed by this process.
+        app_state.exercises[0].skipped = true;
+
+        // Another process set all exercises to done.
+        // `x` and `y` are unknown, for example because they are ignored.
+        let content = [STATE_FILE_HEADER, b"a\n\na\nb\nc\ny\nx\n\n\nx"].concat();
+        let unknown = app_state.merge_progress(&content);
+
+        assert!(!app_state.exercises[0].done);
+        assert!(app_state.exercises[0].skipped);
+        assert!(app_state.exercises[1].done);
+        assert!(app_state.exercises[2].done);
+        assert_eq!(app_state.n_done, 2);
+        assert_eq!(unknown.done, [b"x", b"y"]);
+        assert!(unknown.skipped.is_empty());
+        assert_eq!(unknown.viewed_hints, [b"x"]);
+
+        // The unknown progress is kept.
+        app_state.file_buf = STATE_FILE_HEADER.to_vec();
+        app_state.fill_file_buf(&unknown);
+        assert_eq!(
+            app_state.file_buf,
+            [STATE_FILE_HEADER, b"a\n\nb\nc\nx\ny\n\na\n\nx"].concat(),
+        );
+    }
+
+    #[test]
+    fn state_file_round_trip() {
+        let mut app_state = dummy_app_state(
+            ["a", "b", "c", "d"]
+                .map(|name| Exercise {
+                    name,
+                    ..dummy_exercise()
+                })
+                .into(),
+        );
+        app_state.file_buf = STATE_FILE_HEADER.to_vec();
+        app_state.current_exercise_ind = 1;
+        app_state.exercises[0].done = true;
+        app_state.exercises[1].hint_viewed = true;
+        app_state.exercises[3].hint_viewed = true;
+
+        for skipped in [false, true] {
+            app_state.exercises[2].skipped = skipped;
+            app_state.fill_file_buf(&UnknownProgress::default());
+            let ParsedStateFile::Valid {
+                current_exercise_name,
+                done_exercises,
+                skipped_exercises,
+                viewed_hints,
+            } = parse_state_file(&app_state.file_buf)
+            else {
+                panic!("Invalid state file");
+            };
+            assert_eq!(current_exercise_name, b"b");
+            assert_eq!(done_exercises, HashSet::from([&b"a"[..]]));
+            assert_eq!(skipped_exercises.contains(&b"c"[..]), skipped);
+            assert_eq!(skipped_exercises.len(), usize::from(skipped));
+            assert_eq!(viewed_hints, HashSet::from([&b"b"[..], b"d"]));
+        }
+    }
+
+    #[test]
+    fn parse_state_files() {
+        let assert_valid = |file: &[u8], skipped: &[&[u8]], hints: &[&[u8]]| {
+            let ParsedStateFile::Valid {
+                current_exercise_name,
+                done_exercises,
+                skipped_exercises,
+                viewed_hints,
+            } = parse_state_file(file)
+            else {
+                panic!("Invalid state file: {}", String::from_utf8_lossy(file));
+            };
+            assert_eq!(current_exercise_name, b"b");
+            assert_eq!(done_exercises, HashSet::from([&b"a"[..], b"c"]));
+            assert_eq!(skipped_exercises, skipped.iter().copied().collect());
+            assert_eq!(viewed_hints, hints.iter().copied().collect());
+        };
+
+        // Version 1 without a version line.
+        assert_valid(b"DON'T EDIT THIS FILE!\n\nb\n\na\nc", &[], &[]);
+        assert_valid(b"DON'T EDIT THIS FILE!\nversion: 2\nb\n\na\nc", &[], &[]);
+        // Version 3 without viewed hints.
+        assert_valid(
+            b"DON'T EDIT THIS FILE!\nversion: 3\nb\n\na\nc\n\nd\ne",
+            &[b"d", b"e"],
+            &[],
+        );
+        assert_valid(&[STATE_FILE_HEADER, b"b\n\na\nc"].concat(), &[], &[]);
+        assert_valid(
+            &[STATE_FILE_HEADER, b"b\n\na\nc\n\nd\ne"].concat(),
+            &[b"d", b"e"],
+            &[],
+        );
+        assert_valid(
+            &[STATE_FILE_HEADER, b"b\n\na\nc\n\nd\n\nf"].concat(),
+            &[b"d"],
+            &[b"f"],
+        );
+        assert_valid(
+            &[STATE_FILE_HEADER, b"b\n\na\nc\n\n\nf"].concat(),
+            &[],
+            &[b"f"],
+        );
+
+        assert!(matches!(parse_state_file(b""), ParsedStateFile::Invalid));
+        assert!(matches!(
+            parse_state_file(b"DON'T EDIT THIS FILE!\nversion: 2\n"),
+            ParsedStateFile::Invalid,
+        ));
+        let newer_version = format!(
+            "DON'T EDIT THIS FILE!\nversion: {}\nb\n\na\nc",
+            STATE_FILE_VERSION + 1,
+        );
+        assert!(matches!(
+            parse_state_file(newer_version.as_bytes()),
+            ParsedStateFile::UnknownVersion,
+        ));
     }
 }
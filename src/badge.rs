@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::app_state::AppState;
+
+const LABEL: &str = "rustlings";
+// Approximate width of a character with the font size 11 in pixels.
+const CHAR_WIDTH: usize = 7;
+// Horizontal padding of each part of the SVG badge in pixels.
+const PADDING: usize = 10;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum BadgeFormat {
+    Svg,
+    /// JSON for the endpoint badges of shields.io.
+    ShieldsJson,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum BadgeColor {
+    Red,
+    Yellow,
+    Green,
+}
+
+impl BadgeColor {
+    // Red below a third, yellow below two thirds and green above.
+    fn from_percentage(percentage: f64) -> Self {
+        if percentage < 100.0 / 3.0 {
+            Self::Red
+        } else if percentage < 200.0 / 3.0 {
+            Self::Yellow
+        } else {
+            Self::Green
+        }
+    }
+
+    // The named color of shields.io.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Red => "red",
+            Self::Yellow => "yellow",
+            Self::Green => "brightgreen",
+        }
+    }
+
+    // The same colors as the ones of shields.io.
+    fn hex(self) -> &'static str {
+        match self {
+            Self::Red => "#e05d44",
+            Self::Yellow => "#dfb317",
+            Self::Green => "#4c1",
+        }
+    }
+}
+
+// https://shields.io/badges/endpoint-badge
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShieldsEndpoint<'a> {
+    schema_version: u8,
+    label: &'a str,
+    message: &'a str,
+    color: &'a str,
+}
+
+fn svg(message: &str, color: BadgeColor) -> String {
+    let label_width = LABEL.len() * CHAR_WIDTH + PADDING;
+    // The message only contains ASCII characters.
+    let message_width = message.len() * CHAR_WIDTH + PADDING;
+    let width = label_width + message_width;
+    let label_x = label_width / 2;
+    let message_x = label_width + message_width / 2;
+    let color = color.hex();
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{LABEL}: {message}">
+  <title>{LABEL}: {message}</title>
+  <rect width="{label_width}" height="20" fill="#555"/>
+  <rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{LABEL}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+/// Render a badge with the completion percentage.
+/// It is written to stdout if no output file is given.
+pub fn badge(app_state: &AppState, format: BadgeFormat, output: Option<&Path>) -> Result<()> {
+    let percentage = app_state.completion_percentage();
+    let message = format!("{percentage:.1}%");
+    let color = BadgeColor::from_percentage(percentage);
+
+    let content = match format {
+        BadgeFormat::Svg => svg(&message, color),
+        BadgeFormat::ShieldsJson => {
+            let mut json = serde_json::to_string_pretty(&ShieldsEndpoint {
+                schema_version: 1,
+                label: LABEL,
+                message: &message,
+                color: color.name(),
+            })
+            .context("Failed to serialize the badge to JSON")?;
+            json.push('\n');
+            json
+        }
+    };
+
+    match output {
+        Some(output) => fs::write(output, content)
+            .with_context(|| format!("Failed to write the file {}", output.display())),
+        None => io::stdout()
+            .lock()
+            .write_all(content.as_bytes())
+            .context("Failed to write the badge to stdout"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_thresholds() {
+        assert_eq!(BadgeColor::from_percentage(0.0), BadgeColor::Red);
+        assert_eq!(BadgeColor::from_percentage(33.3), BadgeColor::Red);
+        assert_eq!(BadgeColor::from_percentage(33.4), BadgeColor::Yellow);
+        assert_eq!(BadgeColor::from_percentage(66.6), BadgeColor::Yellow);
+        assert_eq!(BadgeColor::from_percentage(66.7), BadgeColor::Green);
+        assert_eq!(BadgeColor::from_percentage(100.0), BadgeColor::Green);
+    }
+}
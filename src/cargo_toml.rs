@@ -37,13 +37,10 @@ pub fn append_bins(
         buf.extend_from_slice(exercise_info.name.as_bytes());
         buf.extend_from_slice(b"\", path = \"");
         buf.extend_from_slice(exercise_path_prefix);
-        buf.extend_from_slice(b"exercises/");
-        if let Some(dir) = &exercise_info.dir {
-            buf.extend_from_slice(dir.as_bytes());
-            buf.push(b'/');
-        }
-        buf.extend_from_slice(exercise_info.name.as_bytes());
-        buf.extend_from_slice(b".rs\" },\n");
+        buf.extend_from_slice(exercise_info.path().as_bytes());
+        buf.push(b'"');
+        append_edition(buf, exercise_info);
+        buf.extend_from_slice(b" },\n");
 
         let sol_path = exercise_info.sol_path();
         if !Path::new(&sol_path).exists() {
@@ -55,13 +52,19 @@ pub fn append_bins(
         buf.extend_from_slice(b"_sol");
         buf.extend_from_slice(b"\", path = \"");
         buf.extend_from_slice(exercise_path_prefix);
-        buf.extend_from_slice(b"solutions/");
-        if let Some(dir) = &exercise_info.dir {
-            buf.extend_from_slice(dir.as_bytes());
-            buf.push(b'/');
-        }
-        buf.extend_from_slice(exercise_info.name.as_bytes());
-        buf.extend_from_slice(b".rs\" },\n");
+        buf.extend_from_slice(sol_path.as_bytes());
+        buf.push(b'"');
+        append_edition(buf, exercise_info);
+        buf.extend_from_slice(b" },\n");
+    }
+}
+
+// Override the edition of the package for the bin of an exercise or its solution.
+fn append_edition(buf: &mut Vec<u8>, exercise_info: &ExerciseInfo) {
+    if let Some(edition) = &exercise_info.edition {
+        buf.extend_from_slice(b", edition = \"");
+        buf.extend_from_slice(edition.as_bytes());
+        buf.push(b'"');
     }
 }
 
@@ -88,6 +91,7 @@ pub fn updated_cargo_toml(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_bins_start_end_ind() {
@@ -110,6 +114,19 @@ mod tests {
                 dir: None,
                 test: true,
                 strict_clippy: true,
+                test_filter: None,
+                expected_output: None,
+                expected_exit_code: 0,
+                max_ns: None,
+                style_check: false,
+                tags: Vec::new(),
+                forbidden: Vec::new(),
+                multi_file: false,
+                difficulty: None,
+                estimated_minutes: None,
+                requires: Vec::new(),
+                env: BTreeMap::new(),
+                edition: None,
                 hint: String::new(),
                 skip_check_unsolved: false,
             },
@@ -118,6 +135,19 @@ mod tests {
                 dir: Some(String::from("d")),
                 test: false,
                 strict_clippy: false,
+                test_filter: None,
+                expected_output: None,
+                expected_exit_code: 0,
+                max_ns: None,
+                style_check: false,
+                tags: Vec::new(),
+                forbidden: Vec::new(),
+                multi_file: false,
+                difficulty: None,
+                estimated_minutes: None,
+                requires: Vec::new(),
+                env: BTreeMap::new(),
+                edition: Some(String::from("2018")),
                 hint: String::new(),
                 skip_check_unsolved: false,
             },
@@ -129,7 +159,7 @@ mod tests {
             buf,
             br#"
   { name = "1", path = "exercises/1.rs" },
-  { name = "2", path = "exercises/d/2.rs" },
+  { name = "2", path = "exercises/d/2.rs", edition = "2018" },
 "#,
         );
 
@@ -138,7 +168,7 @@ mod tests {
             br#"abc
 bin = [
   { name = "1", path = "../exercises/1.rs" },
-  { name = "2", path = "../exercises/d/2.rs" },
+  { name = "2", path = "../exercises/d/2.rs", edition = "2018" },
 ]
 123"#,
         );
@@ -0,0 +1,483 @@
+use anyhow::{Context, Result, bail};
+use crossterm::style::{Attribute, ResetColor, SetAttribute};
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, StdoutLock, Write},
+    num::NonZeroUsize,
+    path::Path,
+    process::{Command, ExitCode},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    app_state::AppState,
+    exercise::{OUTPUT_CAPACITY, strip_ansi},
+    export::now_utc_timestamp,
+    i18n,
+    term::QueueStyle,
+};
+
+// Show the output of the checked pending exercises which was captured while checking them.
+fn write_failures(stdout: &mut StdoutLock, app_state: &AppState) -> Result<()> {
+    for (exercise_ind, exercise) in app_state
+        .exercises()
+        .iter()
+        .enumerate()
+        .filter(|(ind, exercise)| !exercise.done && app_state.check_order().contains(ind))
+    {
+        stdout.write_all(b"\n")?;
+        stdout.queue_style(SetAttribute(Attribute::Underlined))?;
+        stdout.write_all(exercise.name.as_bytes())?;
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b" (")?;
+        exercise.terminal_file_link(stdout)?;
+        stdout.write_all(b")\n")?;
+
+        if exercise.missing() {
+            stdout.write_all(b"The exercise file is missing\n")?;
+            continue;
+        }
+
+        stdout.write_all(app_state.check_output(exercise_ind))?;
+    }
+
+    Ok(())
+}
+
+// Escape text for XML attributes and elements.
+// Control characters which aren't allowed in XML 1.0 are removed.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write a JUnit XML report with a test case for each checked exercise.
+/// The output of pending exercises captured while checking them is included in the `<failure>` element.
+/// Must be called after checking all exercises.
+pub fn write_junit_report(app_state: &AppState, path: &Path) -> Result<()> {
+    let checked = app_state
+        .check_order()
+        .iter()
+        .map(|&exercise_ind| (exercise_ind, &app_state.exercises()[exercise_ind]))
+        .collect::<Vec<_>>();
+    let n_failures = checked
+        .iter()
+        .filter(|(_, exercise)| !exercise.done && !exercise.skipped)
+        .count();
+    let n_skipped = checked
+        .iter()
+        .filter(|(_, exercise)| !exercise.done && exercise.skipped)
+        .count();
+    let check_durations = app_state.check_durations();
+    let duration_secs = |exercise_ind: usize| {
+        check_durations
+            .get(exercise_ind)
+            .map_or(0.0, |d| d.as_secs_f64())
+    };
+    // Not `sum` which returns -0.0 for no exercises.
+    let total_secs = checked.iter().fold(0.0, |secs, &(exercise_ind, _)| {
+        secs + duration_secs(exercise_ind)
+    });
+
+    let mut report = String::with_capacity(OUTPUT_CAPACITY);
+    report.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    // Writing to a `String` can't fail.
+    let _ = writeln!(
+        report,
+        "<testsuites name=\"rustlings\" tests=\"{}\" failures=\"{n_failures}\" skipped=\"{n_skipped}\" time=\"{total_secs:.3}\">",
+        checked.len(),
+    );
+    let _ = writeln!(
+        report,
+        "  <testsuite name=\"rustlings\" tests=\"{}\" failures=\"{n_failures}\" skipped=\"{n_skipped}\" time=\"{total_secs:.3}\" timestamp=\"{}\">",
+        checked.len(),
+        now_utc_timestamp(),
+    );
+
+    for (exercise_ind, exercise) in checked {
+        let classname = exercise.dir.unwrap_or("exercises");
+        let _ = write!(
+            report,
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            xml_escape(exercise.name),
+            xml_escape(classname),
+            duration_secs(exercise_ind),
+        );
+
+        if exercise.done {
+            report.push_str("/>\n");
+            continue;
+        }
+        report.push_str(">\n");
+
+        if exercise.skipped {
+            report.push_str("      <skipped message=\"The exercise is skipped\"/>\n");
+        } else if exercise.missing() {
+            report.push_str("      <failure message=\"The exercise file is missing\"/>\n");
+        } else {
+            let output = String::from_utf8_lossy(app_state.check_output(exercise_ind));
+            let _ = writeln!(
+                report,
+                "      <failure message=\"The exercise isn't done yet\">{}</failure>",
+                xml_escape(&strip_ansi(&output)),
+            );
+        }
+
+        report.push_str("    </testcase>\n");
+    }
+
+    report.push_str("  </testsuite>\n</testsuites>\n");
+
+    fs::write(path, report)
+        .with_context(|| format!("Failed to write the report {}", path.display()))
+}
+
+// Whether the changed path belongs to the exercise with the given path.
+// Any file in the directory of a multi-file exercise belongs to it.
+fn is_exercise_path(exercise_path: &str, multi_file: bool, changed_path: &str) -> bool {
+    if multi_file {
+        if let Some(dir) = exercise_path.strip_suffix("main.rs") {
+            return changed_path.starts_with(dir);
+        }
+    }
+
+    changed_path == exercise_path
+}
+
+// Indices of the exercises whose files have unstaged or staged changes according to Git.
+fn changed_exercise_inds(app_state: &AppState) -> Result<Vec<usize>> {
+    let mut changed_paths = Vec::new();
+
+    for staged in [false, true] {
+        let mut cmd = Command::new("git");
+        // `--relative` makes the paths relative to the current directory.
+        cmd.arg("diff").arg("--name-only").arg("--relative");
+        if staged {
+            cmd.arg("--cached");
+        }
+
+        let output = cmd
+            .output()
+            .context("Failed to run the command `git diff …`. Is Git installed?")?;
+        if !output.status.success() {
+            bail!(
+                "Failed to get the changed files from Git. Is this directory in a Git repository?\n{}",
+                String::from_utf8_lossy(&output.stderr).trim_end(),
+            );
+        }
+
+        changed_paths.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(String::from),
+        );
+    }
+
+    Ok(app_state
+        .exercises()
+        .iter()
+        .enumerate()
+        .filter(|(_, exercise)| {
+            changed_paths
+                .iter()
+                .any(|path| is_exercise_path(exercise.path, exercise.multi_file, path))
+        })
+        .map(|(ind, _)| ind)
+        .collect())
+}
+
+// Note that the check was stopped early because of the maximum number of failures.
+fn write_unchecked(
+    stdout: &mut StdoutLock,
+    n_unchecked: usize,
+    max_failures: Option<NonZeroUsize>,
+) -> io::Result<()> {
+    let Some(max_failures) = max_failures else {
+        return Ok(());
+    };
+    if n_unchecked == 0 {
+        return Ok(());
+    }
+
+    writeln!(
+        stdout,
+        "\nThe maximum number of failures ({max_failures}) was reached. {n_unchecked} exercises weren't checked",
+    )
+}
+
+fn write_durations(stdout: &mut StdoutLock, app_state: &AppState) -> io::Result<()> {
+    let name_width = app_state
+        .exercises()
+        .iter()
+        .map(|exercise| exercise.name.len())
+        .max()
+        .unwrap_or(0);
+
+    for (_, (exercise, duration)) in app_state
+        .exercises()
+        .iter()
+        .zip(app_state.check_durations())
+        .enumerate()
+        .filter(|(ind, _)| app_state.check_order().contains(ind))
+    {
+        writeln!(
+            stdout,
+            "{:name_width$}  {:>6.2}s",
+            exercise.name,
+            duration.as_secs_f64(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Which exercises to check. All exercises by default.
+#[derive(Default)]
+pub struct Selection<'a> {
+    /// Only the exercises whose files have unstaged or staged changes according to Git.
+    pub changed: bool,
+    /// Only the exercise with this name and the ones after it.
+    pub from: Option<&'a str>,
+    /// Only the exercises with this tag.
+    pub tag: Option<&'a str>,
+}
+
+/// How to check the selected exercises and what to print.
+#[derive(Default)]
+pub struct Options {
+    /// Check the exercises in a random order.
+    pub shuffle: bool,
+    /// The seed of the random order. A random seed by default.
+    pub seed: Option<u64>,
+    /// Print how long checking each exercise took.
+    pub verbose: bool,
+    /// Print the output of the pending exercises.
+    pub show_failures: bool,
+    /// Only print the failures.
+    pub quiet: bool,
+    /// Remove the build artifacts first to build all exercises from scratch.
+    pub clean: bool,
+    /// Stop checking further exercises once this many exercises are pending.
+    pub max_failures: Option<NonZeroUsize>,
+}
+
+pub fn check_all(
+    app_state: &mut AppState,
+    selection: Selection,
+    options: Options,
+) -> Result<ExitCode> {
+    let Options {
+        shuffle,
+        seed,
+        verbose,
+        show_failures,
+        quiet,
+        clean,
+        max_failures,
+    } = options;
+
+    if let Some(from) = selection.from {
+        let from_ind = app_state.exercise_ind_by_name(from)?;
+        let exercise_inds = (from_ind..app_state.exercises().len()).collect::<Vec<_>>();
+        app_state.restrict_check_order(&exercise_inds);
+    }
+
+    if let Some(tag) = selection.tag {
+        let exercise_inds = app_state.tag_exercise_inds(tag)?;
+        app_state.restrict_check_order(&exercise_inds);
+    }
+
+    if selection.changed {
+        let exercise_inds = changed_exercise_inds(app_state)?;
+        app_state.restrict_check_order(&exercise_inds);
+        if app_state.check_order().is_empty() {
+            if !quiet {
+                println!("No exercise files to check have changed according to Git");
+            }
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
+
+    // For example, no exercise after `from` has the `tag`.
+    if app_state.check_order().is_empty() {
+        if !quiet {
+            println!("{}", i18n::tr("check_all_nothing_to_check"));
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if clean {
+        app_state.cmd_runner().clean()?;
+    }
+
+    let seed = shuffle.then(|| {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos() as u64)
+        });
+        app_state.shuffle_check_order(seed);
+        if !quiet {
+            println!("Shuffled with the seed {seed}");
+        }
+        seed
+    });
+
+    let mut stdout = io::stdout().lock();
+    let n_to_check = app_state.check_order().len();
+    let start = Instant::now();
+    let first_pending_exercise_ind =
+        app_state.check_all_exercises(&mut stdout, quiet, max_failures)?;
+    let elapsed = start.elapsed();
+    // Less exercises were checked if the maximum number of failures was reached.
+    let n_unchecked = n_to_check - app_state.check_order().len();
+
+    if quiet {
+        // Only print the failures.
+        let Some(first_pending_exercise_ind) = first_pending_exercise_ind else {
+            return Ok(ExitCode::SUCCESS);
+        };
+        if app_state.current_exercise().done {
+            app_state.set_current_exercise_ind(first_pending_exercise_ind)?;
+        }
+
+        if let Some(seed) = seed {
+            writeln!(stdout, "Shuffled with the seed {seed}")?;
+        }
+        write_failures(&mut stdout, app_state)?;
+        write_unchecked(&mut stdout, n_unchecked, max_failures)?;
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let exit_code = if let Some(first_pending_exercise_ind) = first_pending_exercise_ind {
+        if app_state.current_exercise().done {
+            app_state.set_current_exercise_ind(first_pending_exercise_ind)?;
+        }
+
+        stdout.write_all(b"\n\n")?;
+        let pending = app_state.n_pending();
+        if pending == 1 {
+            stdout.write_all(i18n::tr("check_all_one_pending").as_bytes())?;
+        } else {
+            let total = app_state.exercises().len();
+            let msg = i18n::tr_args(
+                "check_all_pending",
+                &[
+                    ("pending", &pending.to_string()),
+                    ("total", &total.to_string()),
+                ],
+            );
+            stdout.write_all(msg.as_bytes())?;
+        }
+        app_state
+            .current_exercise()
+            .terminal_file_link(&mut stdout)?;
+        stdout.write_all(b"\n")?;
+
+        if show_failures {
+            write_failures(&mut stdout, app_state)?;
+        }
+        write_unchecked(&mut stdout, n_unchecked, max_failures)?;
+
+        ExitCode::FAILURE
+    } else if app_state
+        .exercises()
+        .iter()
+        .any(|exercise| !exercise.done && !exercise.skipped)
+    {
+        // Only some exercises were checked.
+        let checked = app_state.check_order().len().to_string();
+        stdout.write_all(b"\n\n")?;
+        stdout.write_all(
+            i18n::tr_args("check_all_checked_done", &[("checked", &checked)]).as_bytes(),
+        )?;
+        stdout.write_all(b"\n")?;
+
+        ExitCode::SUCCESS
+    } else {
+        app_state.render_final_message(&mut stdout)?;
+
+        ExitCode::SUCCESS
+    };
+
+    // Don't let skipped exercises be forgotten.
+    let n_skipped = app_state.n_skipped();
+    if n_skipped > 0 {
+        writeln!(stdout, "\nSkipped exercises ({n_skipped}):")?;
+        for exercise in app_state
+            .exercises()
+            .iter()
+            .filter(|exercise| exercise.skipped)
+        {
+            stdout.write_all(b"  ")?;
+            exercise.terminal_file_link(&mut stdout)?;
+            stdout.write_all(b"\n")?;
+        }
+    }
+
+    stdout.write_all(b"\n")?;
+    if verbose {
+        write_durations(&mut stdout, app_state)?;
+    }
+    let seconds = format!("{:.1}", elapsed.as_secs_f64());
+    stdout.write_all(i18n::tr_args("check_all_elapsed", &[("seconds", &seconds)]).as_bytes())?;
+    stdout.write_all(b"\n")?;
+
+    Ok(exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xml_escaping() {
+        assert_eq!(
+            xml_escape("a < b && \"c\" > 'd'"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;",
+        );
+        assert_eq!(xml_escape("line\n\ttab\x1b\x07"), "line\n\ttab");
+    }
+
+    #[test]
+    fn exercise_paths() {
+        assert!(is_exercise_path(
+            "exercises/intro1.rs",
+            false,
+            "exercises/intro1.rs",
+        ));
+        assert!(!is_exercise_path(
+            "exercises/intro1.rs",
+            false,
+            "exercises/intro2.rs",
+        ));
+
+        let path = "exercises/multi_file_success/main.rs";
+        assert!(is_exercise_path(path, true, path));
+        assert!(is_exercise_path(
+            path,
+            true,
+            "exercises/multi_file_success/greeting.rs",
+        ));
+        assert!(!is_exercise_path(
+            path,
+            true,
+            "exercises/multi_file_success2/main.rs",
+        ));
+        assert!(!is_exercise_path(path, true, "exercises/intro1.rs"));
+    }
+}
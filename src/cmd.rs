@@ -1,49 +1,243 @@
 use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 use std::{
-    io::Read,
-    path::PathBuf,
-    process::{Command, Stdio},
+    collections::BTreeMap,
+    env::consts::EXE_SUFFIX,
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::Mutex,
+    thread::{self, Scope},
+    time::Duration,
 };
 
+use crate::term;
+
+/// IDs of the child processes which are currently running.
+/// Used to kill them if the user interrupts Rustlings with Ctrl-C.
+static RUNNING_CHILDREN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+// Registers a child process as running until the guard is dropped.
+struct RunningChildGuard(u32);
+
+impl RunningChildGuard {
+    fn register(id: u32) -> Self {
+        if let Ok(mut running_children) = RUNNING_CHILDREN.lock() {
+            running_children.push(id);
+        }
+
+        Self(id)
+    }
+}
+
+impl Drop for RunningChildGuard {
+    fn drop(&mut self) {
+        if let Ok(mut running_children) = RUNNING_CHILDREN.lock() {
+            if let Some(ind) = running_children.iter().position(|id| *id == self.0) {
+                running_children.swap_remove(ind);
+            }
+        }
+    }
+}
+
+/// Kill all child processes which are still running.
+/// Errors are ignored because a child could have exited in the meantime.
+pub fn kill_running_children() {
+    let Ok(running_children) = RUNNING_CHILDREN.lock() else {
+        return;
+    };
+
+    for &id in running_children.iter() {
+        #[cfg(not(windows))]
+        if let Some(pid) = rustix::process::Pid::from_raw(id as i32) {
+            let _ = rustix::process::kill_process(pid, rustix::process::Signal::Kill);
+        }
+
+        #[cfg(windows)]
+        let _ = Command::new("taskkill")
+            .arg("/F")
+            .arg("/T")
+            .arg("/PID")
+            .arg(id.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+/// Failures with these patterns in the output are caused by transient problems like the linker
+/// still writing the binary. They aren't compilation or test failures.
+const TRANSIENT_FAILURE_PATTERNS: &[&[u8]] =
+    &[b"Text file busy", b"Resource temporarily unavailable"];
+/// How often a command is run at most if it fails because of a transient problem.
+const MAX_ATTEMPTS: u32 = 3;
+/// Multiplied by the number of the failed attempt.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+fn is_transient_failure(res: &Result<bool>, output: Option<&[u8]>) -> bool {
+    match res {
+        Ok(true) => false,
+        Ok(false) => output.is_some_and(|output| {
+            TRANSIENT_FAILURE_PATTERNS.iter().any(|pattern| {
+                output
+                    .windows(pattern.len())
+                    .any(|window| window == *pattern)
+            })
+        }),
+        Err(e) => e
+            .downcast_ref::<io::Error>()
+            .is_some_and(|e| e.kind() == io::ErrorKind::ExecutableFileBusy),
+    }
+}
+
+/// Patterns in the output of Cargo if the Clippy component isn't installed.
+/// The first ones are from rustup, the last one from Cargo without rustup.
+const CLIPPY_MISSING_PATTERNS: &[&[u8]] = &[
+    b"'cargo-clippy' is not installed",
+    b"'cargo-clippy' binary, normally provided by the 'clippy' component",
+    b"no such command: `clippy`",
+];
+
+fn is_clippy_missing(output: &[u8]) -> bool {
+    CLIPPY_MISSING_PATTERNS.iter().any(|pattern| {
+        output
+            .windows(pattern.len())
+            .any(|window| window == *pattern)
+    })
+}
+
 /// Run a command with a description for a possible error and append the merged stdout and stderr.
 /// The boolean in the returned `Result` is true if the command's exit status is success.
-fn run_cmd(mut cmd: Command, description: &str, output: Option<&mut Vec<u8>>) -> Result<bool> {
-    let spawn = |mut cmd: Command| {
-        // NOTE: The closure drops `cmd` which prevents a pipe deadlock.
-        cmd.stdin(Stdio::null())
-            .spawn()
-            .with_context(|| format!("Failed to run the command `{description}`"))
-    };
+/// The command is retried if it fails because of a transient problem.
+#[inline]
+fn run_cmd(cmd: Command, description: &str, output: Option<&mut Vec<u8>>) -> Result<bool> {
+    run_cmd_expecting(cmd, description, output, None, ExitStatus::success)
+}
 
-    let mut handle = if let Some(output) = output {
-        let (mut reader, writer) = os_pipe::pipe().with_context(|| {
-            format!("Failed to create a pipe to run the command `{description}``")
-        })?;
+// Like `run_cmd`, but the boolean is true if `is_expected` returns true for the exit status.
+// If `input` is given, it is written to the command's stdin. Otherwise, stdin is empty.
+fn run_cmd_expecting(
+    mut cmd: Command,
+    description: &str,
+    mut output: Option<&mut Vec<u8>>,
+    input: Option<&[u8]>,
+    is_expected: impl Fn(&ExitStatus) -> bool,
+) -> Result<bool> {
+    log::debug!("Running the command `{description}`: {cmd:?}");
 
-        let writer_clone = writer.try_clone().with_context(|| {
-            format!("Failed to clone the pipe writer for the command `{description}`")
-        })?;
+    let mut attempt = 1;
+    loop {
+        let output_start = output.as_deref().map_or(0, Vec::len);
+        let res = run_cmd_once(
+            &mut cmd,
+            description,
+            output.as_deref_mut(),
+            input,
+            &is_expected,
+        );
+
+        let new_output = output.as_deref().map(|output| &output[output_start..]);
+        if attempt == MAX_ATTEMPTS || !is_transient_failure(&res, new_output) {
+            return res;
+        }
+
+        log::debug!("Retrying the command `{description}` after a transient failure");
+        // Only keep the output of the last attempt.
+        if let Some(output) = output.as_deref_mut() {
+            output.truncate(output_start);
+        }
+        thread::sleep(RETRY_BACKOFF * attempt);
+        attempt += 1;
+    }
+}
 
-        cmd.stdout(writer_clone).stderr(writer);
-        let handle = spawn(cmd)?;
+// Write the input to the stdin of the child in another thread.
+// Writing it before reading the output could deadlock if the child fills the output pipe before
+// reading all of its input.
+fn write_input<'scope>(
+    s: &'scope Scope<'scope, '_>,
+    child: &mut Child,
+    input: Option<&'scope [u8]>,
+    description: &str,
+) -> Result<()> {
+    let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) else {
+        return Ok(());
+    };
 
-        reader
-            .read_to_end(output)
-            .with_context(|| format!("Failed to read the output of the command `{description}`"))?;
+    thread::Builder::new()
+        .spawn_scoped(s, move || {
+            // The child can exit without reading all of its input.
+            // Dropping `stdin` afterwards closes it.
+            let _ = stdin.write_all(input);
+        })
+        .with_context(|| {
+            format!("Failed to spawn a thread to write the input of the command `{description}`")
+        })?;
 
-        output.push(b'\n');
+    Ok(())
+}
 
-        handle
-    } else {
+fn run_cmd_once(
+    cmd: &mut Command,
+    description: &str,
+    output: Option<&mut Vec<u8>>,
+    input: Option<&[u8]>,
+    is_expected: impl Fn(&ExitStatus) -> bool,
+) -> Result<bool> {
+    let spawn = |cmd: &mut Command| {
+        let stdin = if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        };
+        let res = cmd
+            .stdin(stdin)
+            .spawn()
+            .with_context(|| format!("Failed to run the command `{description}`"));
+        // NOTE: Dropping the pipe writers in `cmd` prevents a pipe deadlock.
         cmd.stdout(Stdio::null()).stderr(Stdio::null());
-        spawn(cmd)?
+        res
     };
 
-    handle
-        .wait()
-        .with_context(|| format!("Failed to wait on the command `{description}` to exit"))
-        .map(|status| status.success())
+    thread::scope(|s| {
+        // Keep the guard until the child exits.
+        let (mut handle, _running_child_guard) = if let Some(output) = output {
+            let (mut reader, writer) = os_pipe::pipe().with_context(|| {
+                format!("Failed to create a pipe to run the command `{description}``")
+            })?;
+
+            let writer_clone = writer.try_clone().with_context(|| {
+                format!("Failed to clone the pipe writer for the command `{description}`")
+            })?;
+
+            cmd.stdout(writer_clone).stderr(writer);
+            let mut handle = spawn(cmd)?;
+            let running_child_guard = RunningChildGuard::register(handle.id());
+            write_input(s, &mut handle, input, description)?;
+
+            reader.read_to_end(output).with_context(|| {
+                format!("Failed to read the output of the command `{description}`")
+            })?;
+
+            output.push(b'\n');
+
+            (handle, running_child_guard)
+        } else {
+            cmd.stdout(Stdio::null()).stderr(Stdio::null());
+            let mut handle = spawn(cmd)?;
+            let running_child_guard = RunningChildGuard::register(handle.id());
+            write_input(s, &mut handle, input, description)?;
+
+            (handle, running_child_guard)
+        };
+
+        handle
+            .wait()
+            .with_context(|| format!("Failed to wait on the command `{description}` to exit"))
+            .map(|status| is_expected(&status))
+    })
 }
 
 // Parses parts of the output of `cargo metadata`.
@@ -52,8 +246,31 @@ struct CargoMetadata {
     target_directory: PathBuf,
 }
 
+// Parses parts of the JSON messages of `cargo test --no-run`.
+#[derive(Deserialize)]
+struct CargoArtifact {
+    reason: String,
+    #[serde(default)]
+    profile: CargoArtifactProfile,
+    executable: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Default)]
+struct CargoArtifactProfile {
+    test: bool,
+}
+
+#[derive(Clone)]
 pub struct CmdRunner {
     target_dir: PathBuf,
+    // Show full backtraces of panics in exercises.
+    backtrace: bool,
+    // Print the commands instead of running them.
+    print_only: bool,
+    // Additional flags for `rustc` when compiling exercises.
+    rustc_flags: Vec<String>,
+    // Written to the stdin of the exercise binaries instead of an empty stdin.
+    bin_input: Option<Vec<u8>>,
 }
 
 impl CmdRunner {
@@ -79,11 +296,71 @@ impl CmdRunner {
                 "Failed to read the field `target_directory` from the output of the command `cargo metadata …`",
             )?;
 
+        log::debug!(
+            "Using the target directory {}",
+            metadata.target_directory.display(),
+        );
+
         Ok(Self {
             target_dir: metadata.target_directory,
+            backtrace: false,
+            print_only: false,
+            rustc_flags: Vec::new(),
+            bin_input: None,
         })
     }
 
+    /// A copy of the runner which prints the commands to stdout instead of running them.
+    /// Every command is treated as successful.
+    pub fn print_only(&self) -> Self {
+        Self {
+            print_only: true,
+            ..self.clone()
+        }
+    }
+
+    /// Set `RUST_BACKTRACE=full` for all commands to show the backtraces of panics.
+    #[inline]
+    pub fn set_backtrace(&mut self, backtrace: bool) {
+        self.backtrace = backtrace;
+    }
+
+    /// Pass these flags to `rustc` when compiling exercises.
+    /// They are ignored by Cargo if the environment variable `RUSTFLAGS` is set.
+    #[inline]
+    pub fn set_rustc_flags(&mut self, rustc_flags: Vec<String>) {
+        self.rustc_flags = rustc_flags;
+    }
+
+    /// Write this input to the stdin of the exercise binaries when running them.
+    /// Tests and other commands still get an empty stdin.
+    #[inline]
+    pub fn set_bin_input(&mut self, bin_input: Vec<u8>) {
+        self.bin_input = Some(bin_input);
+    }
+
+    /// Return an error with instructions if Clippy isn't installed.
+    /// Should be called after a failed Clippy command to distinguish this case from lints.
+    /// `clippy_output` is the output of the failed command if it was captured.
+    pub fn ensure_clippy_installed(&self, clippy_output: Option<&[u8]>) -> Result<()> {
+        let clippy_missing = match clippy_output {
+            Some(clippy_output) => is_clippy_missing(clippy_output),
+            None => {
+                let mut cmd = Command::new("cargo");
+                cmd.arg("clippy").arg("--version");
+                let mut output = Vec::with_capacity(256);
+                !run_cmd(cmd, "cargo clippy --version", Some(&mut output))?
+                    && is_clippy_missing(&output)
+            }
+        };
+
+        if clippy_missing {
+            bail!(CLIPPY_MISSING_ERR);
+        }
+
+        Ok(())
+    }
+
     pub fn cargo<'out>(
         &self,
         subcommand: &str,
@@ -101,28 +378,209 @@ impl CmdRunner {
             .arg(&self.target_dir);
 
         if output.is_some() {
-            cmd.arg("--color").arg("always");
+            let color = if term::styling() { "always" } else { "never" };
+            cmd.arg("--color").arg(color);
+        }
+
+        if !self.rustc_flags.is_empty() {
+            // A JSON array of strings is also a valid TOML array.
+            let rustc_flags = serde_json::to_string(&self.rustc_flags)
+                .expect("Serializing a vector of strings can't fail");
+            cmd.arg("--config")
+                .arg(format!("build.rustflags={rustc_flags}"));
+        }
+
+        if self.backtrace {
+            cmd.env("RUST_BACKTRACE", "full");
         }
 
-        CargoSubcommand { cmd, output }
+        CargoSubcommand {
+            cmd,
+            output,
+            print_only: self.print_only,
+        }
     }
 
-    /// The boolean in the returned `Result` is true if the command's exit status is success.
-    pub fn run_debug_bin(&self, bin_name: &str, output: Option<&mut Vec<u8>>) -> Result<bool> {
-        // 7 = "/debug/".len()
-        let mut bin_path =
-            PathBuf::with_capacity(self.target_dir.as_os_str().len() + 7 + bin_name.len());
+    /// Remove the shared target directory of the exercises with `cargo clean`.
+    /// All exercises are built from scratch afterwards.
+    pub fn clean(&self) -> Result<()> {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("clean").arg("-q");
+
+        // A hack to make `cargo run` work when developing Rustlings.
+        #[cfg(debug_assertions)]
+        cmd.arg("--manifest-path")
+            .arg("dev/Cargo.toml")
+            .arg("--target-dir")
+            .arg(&self.target_dir);
+
+        if self.print_only {
+            println!("{cmd:?}");
+            return Ok(());
+        }
+
+        if !run_cmd(cmd, "cargo clean", None)? {
+            bail!(
+                "The command `cargo clean` failed to remove the target directory {}",
+                self.target_dir.display(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// The boolean in the returned `Result` is true if the binary exits with the expected code.
+    /// The environment variables in `env` are only set for the binary's process.
+    pub fn run_debug_bin(
+        &self,
+        bin_name: &str,
+        expected_exit_code: i32,
+        env: &BTreeMap<String, String>,
+        output: Option<&mut Vec<u8>>,
+    ) -> Result<bool> {
+        self.run_profile_bin("debug", bin_name, expected_exit_code, env, output)
+    }
+
+    /// Like `run_debug_bin`, but for a binary which was built with `--release`.
+    /// The exit code must be 0.
+    pub fn run_release_bin(
+        &self,
+        bin_name: &str,
+        env: &BTreeMap<String, String>,
+        output: Option<&mut Vec<u8>>,
+    ) -> Result<bool> {
+        self.run_profile_bin("release", bin_name, 0, env, output)
+    }
+
+    fn run_profile_bin(
+        &self,
+        profile_dir: &str,
+        bin_name: &str,
+        expected_exit_code: i32,
+        env: &BTreeMap<String, String>,
+        output: Option<&mut Vec<u8>>,
+    ) -> Result<bool> {
+        self.run_bin(
+            &self.profile_bin_path(profile_dir, bin_name),
+            expected_exit_code,
+            env,
+            output,
+        )
+    }
+
+    fn profile_bin_path(&self, profile_dir: &str, bin_name: &str) -> PathBuf {
+        // 2 = "/".len() + "/".len()
+        let mut bin_path = PathBuf::with_capacity(
+            self.target_dir.as_os_str().len()
+                + 2
+                + profile_dir.len()
+                + bin_name.len()
+                + EXE_SUFFIX.len(),
+        );
         bin_path.push(&self.target_dir);
-        bin_path.push("debug");
+        bin_path.push(profile_dir);
         bin_path.push(bin_name);
+        // `.exe` on Windows.
+        bin_path.as_mut_os_string().push(EXE_SUFFIX);
+
+        bin_path
+    }
+
+    /// The path of an exercise binary which was built without `--release`.
+    #[inline]
+    pub fn debug_bin_path(&self, bin_name: &str) -> PathBuf {
+        self.profile_bin_path("debug", bin_name)
+    }
+
+    /// Build the test binary of an exercise without running it and return its path.
+    /// Returns `None` if the build fails.
+    pub fn build_test_bin(&self, bin_name: &str) -> Result<Option<PathBuf>> {
+        let mut output = Vec::new();
+        let mut test_cmd = self.cargo("test", bin_name, Some(&mut output));
+        test_cmd.args(["--no-run", "--message-format", "json"]);
+        if !test_cmd.run("cargo test --no-run …")? {
+            return Ok(None);
+        }
+
+        let test_bin_path = output.split(|c| *c == b'\n').find_map(|line| {
+            let artifact: CargoArtifact = serde_json::from_slice(line).ok()?;
+            if artifact.reason == "compiler-artifact" && artifact.profile.test {
+                artifact.executable
+            } else {
+                None
+            }
+        });
+
+        Ok(test_bin_path)
+    }
+
+    // The boolean in the returned `Result` is true if the binary exits with the expected code.
+    fn run_bin(
+        &self,
+        bin_path: &Path,
+        expected_exit_code: i32,
+        env: &BTreeMap<String, String>,
+        output: Option<&mut Vec<u8>>,
+    ) -> Result<bool> {
+        let mut cmd = Command::new(bin_path);
+        if self.backtrace {
+            cmd.env("RUST_BACKTRACE", "full");
+        }
+        cmd.envs(env);
+
+        if self.print_only {
+            println!("{cmd:?}");
+            return Ok(true);
+        }
+
+        run_cmd_expecting(
+            cmd,
+            &bin_path.to_string_lossy(),
+            output,
+            self.bin_input.as_deref(),
+            |status| status.code() == Some(expected_exit_code),
+        )
+    }
+
+    /// Compile a standalone Rust file with `rustc` and run the binary if the compilation succeeds.
+    /// The binary is placed in the directory `rustlings-scratch` in the target directory.
+    /// The boolean in the returned `Result` is true if both the compilation and the run succeed.
+    pub fn compile_and_run_file(&self, file_path: &Path, output: &mut Vec<u8>) -> Result<bool> {
+        let bin_name = file_path
+            .file_stem()
+            .with_context(|| format!("The path {} has no file name", file_path.display()))?;
+        let bin_dir = self.target_dir.join("rustlings-scratch");
+        let bin_path = bin_dir.join(bin_name);
+
+        let mut cmd = Command::new("rustc");
+        cmd.arg("--edition")
+            .arg("2024")
+            .arg("--color")
+            .arg(if term::styling() { "always" } else { "never" })
+            .arg("-o")
+            .arg(&bin_path)
+            .args(&self.rustc_flags)
+            .arg(file_path);
+
+        if self.print_only {
+            println!("{cmd:?}");
+        } else {
+            fs::create_dir_all(&bin_dir)
+                .with_context(|| format!("Failed to create the directory {}", bin_dir.display()))?;
+
+            if !run_cmd(cmd, "rustc …", Some(output))? {
+                return Ok(false);
+            }
+        }
 
-        run_cmd(Command::new(&bin_path), &bin_path.to_string_lossy(), output)
+        self.run_bin(&bin_path, 0, &BTreeMap::new(), Some(output))
     }
 }
 
 pub struct CargoSubcommand<'out> {
     cmd: Command,
     output: Option<&'out mut Vec<u8>>,
+    print_only: bool,
 }
 
 impl CargoSubcommand<'_> {
@@ -135,9 +593,36 @@ impl CargoSubcommand<'_> {
         self
     }
 
+    /// Set environment variables only for this command.
+    #[inline]
+    pub fn envs(&mut self, env: &BTreeMap<String, String>) -> &mut Self {
+        self.cmd.envs(env);
+        self
+    }
+
+    /// Let the compiler report its diagnostics as JSON messages to the output.
+    /// Each message contains the human-readable rendering of its diagnostic.
+    /// Nothing changes if the output is discarded.
+    pub fn json_diagnostics(&mut self) -> &mut Self {
+        if self.output.is_some() {
+            let format = if term::styling() {
+                "json-diagnostic-rendered-ansi"
+            } else {
+                "json"
+            };
+            self.cmd.arg("--message-format").arg(format);
+        }
+        self
+    }
+
     /// The boolean in the returned `Result` is true if the command's exit status is success.
     #[inline]
     pub fn run(self, description: &str) -> Result<bool> {
+        if self.print_only {
+            println!("{:?}", self.cmd);
+            return Ok(true);
+        }
+
         run_cmd(self.cmd, description, self.output)
     }
 }
@@ -146,6 +631,10 @@ const CARGO_METADATA_ERR: &str = "Failed to run the command `cargo metadata …`
 Did you already install Rust?
 Try running `cargo --version` to diagnose the problem.";
 
+const CLIPPY_MISSING_ERR: &str =
+    "Clippy isn't installed, but it is required to check the exercises.
+Install it by running `rustup component add clippy`";
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +649,38 @@ mod tests {
 
         assert_eq!(output, b"Hello\n\n");
     }
+
+    #[test]
+    fn transient_failures() {
+        assert!(!is_transient_failure(&Ok(true), Some(b"Text file busy")));
+        assert!(!is_transient_failure(&Ok(false), None));
+        assert!(!is_transient_failure(
+            &Ok(false),
+            Some(b"error[E0308]: mismatched types"),
+        ));
+        assert!(is_transient_failure(
+            &Ok(false),
+            Some(b"error: Text file busy (os error 26)"),
+        ));
+
+        // Only the output of the last attempt is kept.
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo Text file busy; exit 1");
+        let mut output = Vec::new();
+        assert!(!run_cmd(cmd, "sh …", Some(&mut output)).unwrap());
+        assert_eq!(output, b"Text file busy\n\n");
+    }
+
+    #[test]
+    fn clippy_missing() {
+        assert!(is_clippy_missing(
+            b"error: 'cargo-clippy' is not installed for the toolchain 'stable-x86_64-unknown-linux-gnu'.\n\
+              To install, run `rustup component add clippy --toolchain stable-x86_64-unknown-linux-gnu`",
+        ));
+        assert!(is_clippy_missing(b"error: no such command: `clippy`"));
+        assert!(!is_clippy_missing(
+            b"warning: this looks like you are trying to swap `a` and `b`\n\
+              error: could not compile `exercises` (bin \"clippy1\") due to 1 previous error",
+        ));
+    }
 }
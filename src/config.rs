@@ -0,0 +1,88 @@
+use anyhow::{Context, Error, Result, bail};
+use serde::Deserialize;
+use std::{fs, io::ErrorKind};
+
+/// The optional project-level config file in the exercises directory.
+pub const CONFIG_FILE_NAME: &str = "rustlings.toml";
+
+// Flags which Rustlings or Cargo already set for every exercise.
+const RESERVED_RUSTC_FLAGS: &[&str] = &[
+    "--edition",
+    "--error-format",
+    "--json",
+    "--color",
+    "--crate-name",
+    "--crate-type",
+    "--out-dir",
+    "-o",
+];
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Flags which are passed to `rustc` when compiling any exercise.
+    #[serde(default)]
+    pub rustc_flags: Vec<String>,
+    /// An exercise can only be done after viewing its hint.
+    #[serde(default)]
+    pub teach_mode: bool,
+}
+
+impl Config {
+    /// Parse the config file if it exists. Otherwise, return the default config.
+    pub fn parse() -> Result<Self> {
+        let config = match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(file_content) => toml_edit::de::from_str::<Self>(&file_content)
+                .with_context(|| format!("Failed to parse the `{CONFIG_FILE_NAME}` file"))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(
+                    Error::from(e).context(format!("Failed to read the `{CONFIG_FILE_NAME}` file"))
+                );
+            }
+        };
+
+        check_rustc_flags(&config.rustc_flags)?;
+
+        Ok(config)
+    }
+}
+
+fn check_rustc_flags(rustc_flags: &[String]) -> Result<()> {
+    for flag in rustc_flags {
+        let name = flag.split_once('=').map_or(flag.as_str(), |(name, _)| name);
+        // `-o` can be directly followed by its value.
+        if RESERVED_RUSTC_FLAGS.contains(&name) || (name.starts_with("-o") && name != "-o") {
+            bail!(
+                "The flag `{flag}` in `rustc_flags` of the `{CONFIG_FILE_NAME}` file conflicts with the flags set by Rustlings",
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(flags: &[&str]) -> Vec<String> {
+        flags.iter().map(|flag| flag.to_string()).collect()
+    }
+
+    #[test]
+    fn reserved_flags() {
+        assert!(check_rustc_flags(&flags(&["-Zunstable-options", "-Dwarnings"])).is_ok());
+        assert!(check_rustc_flags(&flags(&["--cfg", "feature=\"x\""])).is_ok());
+
+        for flag in [
+            "--edition=2021",
+            "--edition",
+            "--error-format=short",
+            "-o",
+            "-ofile",
+        ] {
+            assert!(check_rustc_flags(&flags(&[flag])).is_err(), "{flag}");
+        }
+    }
+}
@@ -10,15 +10,16 @@ use std::{
 };
 
 use crate::{
-    CURRENT_FORMAT_VERSION,
     cargo_toml::{BINS_BUFFER_CAPACITY, append_bins, bins_start_end_ind},
     cmd::CmdRunner,
     exercise::{OUTPUT_CAPACITY, RunnableExercise},
-    info_file::{ExerciseInfo, InfoFile},
+    info_file::{CURRENT_FORMAT_VERSION, ExerciseInfo, InfoFile},
 };
 
 const MAX_N_EXERCISES: usize = 999;
 const MAX_EXERCISE_NAME_LEN: usize = 32;
+// The editions which can be set for an exercise.
+const EDITIONS: [&str; 4] = ["2015", "2018", "2021", "2024"];
 
 // Find a char that isn't allowed in the exercise's `name` or `dir`.
 fn forbidden_char(input: &str) -> Option<char> {
@@ -128,6 +129,48 @@ fn check_info_file_exercises(info_file: &InfoFile) -> Result<HashSet<PathBuf>> {
             );
         }
 
+        if exercise_info.forbidden.iter().any(String::is_empty) {
+            bail!(
+                "The exercise `{name}` has an empty pattern in `forbidden` in the `info.toml` file"
+            );
+        }
+
+        if exercise_info
+            .edition
+            .as_deref()
+            .is_some_and(|edition| !EDITIONS.contains(&edition))
+        {
+            bail!(
+                "The exercise `{name}` has an unknown `edition` in the `info.toml` file. Supported editions: {}",
+                EDITIONS.join(", "),
+            );
+        }
+
+        if exercise_info
+            .env
+            .keys()
+            .any(|key| key.is_empty() || key.contains(['=', '\0']))
+        {
+            bail!(
+                "The exercise `{name}` has an invalid name of an environment variable in `env` in the `info.toml` file"
+            );
+        }
+
+        if exercise_info
+            .difficulty
+            .is_some_and(|difficulty| !(1..=5).contains(&difficulty))
+        {
+            bail!(
+                "The exercise `{name}` has a `difficulty` outside of the range from 1 to 5 in the `info.toml` file"
+            );
+        }
+
+        if exercise_info.estimated_minutes == Some(0) {
+            bail!(
+                "The exercise `{name}` has `estimated_minutes = 0` in the `info.toml` file. Remove it if the time is unknown"
+            );
+        }
+
         file_buf.clear();
 
         paths.insert(PathBuf::from(path));
@@ -138,7 +181,7 @@ fn check_info_file_exercises(info_file: &InfoFile) -> Result<HashSet<PathBuf>> {
 
 // Check `dir` for unexpected files.
 // Only Rust files in `allowed_rust_files` and `README.md` files are allowed.
-// Only one level of directory nesting is allowed.
+// Only one level of directory nesting is allowed, apart from the directories of multi-file exercises.
 fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> Result<()> {
     let unexpected_file = |path: &Path| {
         anyhow!(
@@ -146,6 +189,9 @@ fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> R
             path.display()
         )
     };
+    // All files in the directory of a multi-file exercise are allowed.
+    let is_multi_file_exercise_dir =
+        |dir_path: &Path| allowed_rust_files.contains(&dir_path.join("main.rs"));
 
     for entry in read_dir(dir).with_context(|| format!("Failed to open the `{dir}` directory"))? {
         let entry = entry.with_context(|| format!("Failed to read the `{dir}` directory"))?;
@@ -165,6 +211,10 @@ fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> R
         }
 
         let dir_path = entry.path();
+        if is_multi_file_exercise_dir(&dir_path) {
+            continue;
+        }
+
         for entry in read_dir(&dir_path)
             .with_context(|| format!("Failed to open the directory {}", dir_path.display()))?
         {
@@ -173,6 +223,10 @@ fn check_unexpected_files(dir: &str, allowed_rust_files: &HashSet<PathBuf>) -> R
             let path = entry.path();
 
             if !entry.file_type().unwrap().is_file() {
+                if is_multi_file_exercise_dir(&path) {
+                    continue;
+                }
+
                 bail!(
                     "Found `{}` but expected only files. Only one level of exercise nesting is allowed",
                     path.display()
@@ -220,9 +274,8 @@ fn check_exercises_unsolved(
     let n_handles = handles.len();
     write!(stdout, "Progress: 0/{n_handles}")?;
     stdout.flush()?;
-    let mut handle_num = 1;
 
-    for (exercise_name, handle) in handles {
+    for (handle_num, (exercise_name, handle)) in (1..).zip(handles) {
         let Ok(result) = handle.join() else {
             bail!("Panic while trying to run the exercise {exercise_name}");
         };
@@ -237,7 +290,6 @@ fn check_exercises_unsolved(
 
         write!(stdout, "\rProgress: {handle_num}/{n_handles}")?;
         stdout.flush()?;
-        handle_num += 1;
     }
     stdout.write_all(b"\n")?;
 
@@ -321,9 +373,9 @@ fn check_solutions(
     let n_handles = handles.len();
     write!(stdout, "Progress: 0/{n_handles}")?;
     stdout.flush()?;
-    let mut handle_num = 1;
 
-    for (exercise_info, handle) in info_file.exercises.iter().zip(handles) {
+    for (handle_num, (exercise_info, handle)) in (1..).zip(info_file.exercises.iter().zip(handles))
+    {
         let Ok(check_result) = handle.join() else {
             bail!(
                 "Panic while trying to run the solution of the exercise {}",
@@ -350,7 +402,6 @@ fn check_solutions(
 
         write!(stdout, "\rProgress: {handle_num}/{n_handles}")?;
         stdout.flush()?;
-        handle_num += 1;
     }
     stdout.write_all(b"\n")?;
 
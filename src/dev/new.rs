@@ -6,7 +6,7 @@ use std::{
     process::Command,
 };
 
-use crate::{CURRENT_FORMAT_VERSION, init::RUST_ANALYZER_TOML};
+use crate::{info_file::CURRENT_FORMAT_VERSION, init::RUST_ANALYZER_TOML};
 
 // Create a directory relative to the current directory and print its path.
 fn create_rel_dir(dir_name: &str, current_dir: &str) -> Result<()> {
@@ -79,6 +79,10 @@ pub fn new(path: &Path, no_git: bool) -> Result<()> {
 }
 
 pub const GITIGNORE: &[u8] = b".rustlings-state.txt
+.rustlings-state.txt.bak
+.rustlings-state.txt.tmp
+.rustlings-state.txt.lock
+.rustlings-history.jsonl
 Cargo.lock
 target/
 .vscode/
@@ -112,6 +116,11 @@ name = "???"
 # Otherwise, the path is `exercises/NAME.rs`
 # dir = "???"
 
+# You can optionally set `multi_file` to `true` (the default is `false`) for an exercise which
+# consists of multiple files. Its main file is then `main.rs` in the directory `NAME/` which can
+# contain further module files (e.g. `exercises/DIR/NAME/main.rs` with `mod helper;`).
+# multi_file = false
+
 # Rustlings expects the exercise to contain tests and run them.
 # You can optionally disable testing by setting `test` to `false` (the default is `true`).
 # In that case, the exercise will be considered done when it just successfully compiles.
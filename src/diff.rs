@@ -0,0 +1,275 @@
+use crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
+use std::io::{self, StdoutLock, Write};
+
+use crate::term::QueueStyle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change<T> {
+    Equal(T),
+    Delete(T),
+    Insert(T),
+}
+
+/// Compute the changes from `old` to `new` based on their longest common subsequence.
+pub fn diff<T: Copy + PartialEq>(old: &[T], new: &[T]) -> Vec<Change<T>> {
+    let n_cols = new.len() + 1;
+    // `lcs_lens[i * n_cols + j]` is the length of the longest common subsequence
+    // of `old[i..]` and `new[j..]`.
+    let mut lcs_lens = vec![0_u32; (old.len() + 1) * n_cols];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs_lens[i * n_cols + j] = if old[i] == new[j] {
+                lcs_lens[(i + 1) * n_cols + j + 1] + 1
+            } else {
+                lcs_lens[(i + 1) * n_cols + j].max(lcs_lens[i * n_cols + j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            changes.push(Change::Equal(new[j]));
+            i += 1;
+            j += 1;
+        } else if lcs_lens[(i + 1) * n_cols + j] >= lcs_lens[i * n_cols + j + 1] {
+            changes.push(Change::Delete(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Insert(new[j]));
+            j += 1;
+        }
+    }
+    changes.extend(old[i..].iter().map(|token| Change::Delete(*token)));
+    changes.extend(new[j..].iter().map(|token| Change::Insert(*token)));
+
+    changes
+}
+
+/// Split a line into words, whitespace runs and single punctuation characters.
+pub fn words(line: &str) -> Vec<&str> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Class {
+        Word,
+        Whitespace,
+        Punctuation,
+    }
+
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Whitespace
+        } else {
+            Class::Punctuation
+        }
+    };
+
+    let mut words = Vec::with_capacity(16);
+    let mut start = 0;
+    let mut prev_class = Class::Punctuation;
+    for (ind, c) in line.char_indices() {
+        let c_class = class(c);
+        if ind > start && (c_class != prev_class || c_class == Class::Punctuation) {
+            words.push(&line[start..ind]);
+            start = ind;
+        }
+        prev_class = c_class;
+    }
+
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+
+    words
+}
+
+fn write_gutter(stdout: &mut StdoutLock, line_num: Option<usize>) -> io::Result<()> {
+    stdout
+        .queue_style(SetForegroundColor(Color::Blue))?
+        .queue_style(SetAttribute(Attribute::Bold))?;
+    match line_num {
+        Some(line_num) => write!(stdout, "{line_num:>4}")?,
+        None => stdout.write_all(b"   -")?,
+    }
+    stdout.queue_style(ResetColor)?;
+    stdout.queue_style(SetForegroundColor(Color::Blue))?;
+    stdout.write_all(" │ ".as_bytes())?;
+    stdout.queue_style(ResetColor)?;
+    Ok(())
+}
+
+fn write_deleted(stdout: &mut StdoutLock, text: &str) -> io::Result<()> {
+    stdout
+        .queue_style(SetForegroundColor(Color::Red))?
+        .queue_style(SetAttribute(Attribute::CrossedOut))?;
+    stdout.write_all(text.as_bytes())?;
+    stdout.queue_style(ResetColor)?;
+    Ok(())
+}
+
+fn write_inserted(stdout: &mut StdoutLock, text: &str) -> io::Result<()> {
+    stdout
+        .queue_style(SetForegroundColor(Color::Green))?
+        .queue_style(SetAttribute(Attribute::Bold))?;
+    stdout.write_all(text.as_bytes())?;
+    stdout.queue_style(ResetColor)?;
+    Ok(())
+}
+
+fn write_changed_line(stdout: &mut StdoutLock, old: &str, new: &str) -> io::Result<()> {
+    for change in diff(&words(old), &words(new)) {
+        match change {
+            Change::Equal(word) => stdout.write_all(word.as_bytes())?,
+            Change::Delete(word) => write_deleted(stdout, word)?,
+            Change::Insert(word) => write_inserted(stdout, word)?,
+        }
+    }
+
+    stdout.write_all(b"\n")
+}
+
+/// Show the lines of `new` which differ from `old` with the changed words highlighted.
+/// Returns `false` if there are no changes.
+pub fn write_word_diff(stdout: &mut StdoutLock, old: &str, new: &str) -> io::Result<bool> {
+    let changes = diff(
+        &old.lines().collect::<Vec<_>>(),
+        &new.lines().collect::<Vec<_>>(),
+    );
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Equal(_)))
+    {
+        return Ok(false);
+    }
+
+    let mut line_num = 1;
+    let mut changes = changes.into_iter().peekable();
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+    loop {
+        // Collect a block of consecutive changed lines.
+        while let Some(change) = changes.next_if(|change| !matches!(change, Change::Equal(_))) {
+            match change {
+                Change::Delete(line) => deleted.push(line),
+                Change::Insert(line) => inserted.push(line),
+                Change::Equal(_) => unreachable!(),
+            }
+        }
+
+        // Pair deleted and inserted lines to show the changed words in between.
+        for ind in 0..deleted.len().max(inserted.len()) {
+            match (deleted.get(ind), inserted.get(ind)) {
+                (Some(old_line), Some(new_line)) => {
+                    write_gutter(stdout, Some(line_num))?;
+                    write_changed_line(stdout, old_line, new_line)?;
+                    line_num += 1;
+                }
+                (Some(old_line), None) => {
+                    write_gutter(stdout, None)?;
+                    write_deleted(stdout, old_line)?;
+                    stdout.write_all(b"\n")?;
+                }
+                (None, Some(new_line)) => {
+                    write_gutter(stdout, Some(line_num))?;
+                    write_inserted(stdout, new_line)?;
+                    stdout.write_all(b"\n")?;
+                    line_num += 1;
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+        deleted.clear();
+        inserted.clear();
+
+        match changes.next() {
+            Some(_) => line_num += 1,
+            None => break,
+        }
+    }
+
+    Ok(true)
+}
+
+/// Show all lines of `old` and `new` prefixed with `-` if they were deleted, `+` if they were
+/// inserted or a space if they are equal.
+pub fn write_line_diff(writer: &mut impl Write, old: &str, new: &str) -> io::Result<()> {
+    let changes = diff(
+        &old.lines().collect::<Vec<_>>(),
+        &new.lines().collect::<Vec<_>>(),
+    );
+
+    for change in changes {
+        match change {
+            Change::Equal(line) => {
+                writer.write_all(b"  ")?;
+                writer.write_all(line.as_bytes())?;
+            }
+            Change::Delete(line) => {
+                writer.queue_style(SetForegroundColor(Color::Red))?;
+                writer.write_all(b"- ")?;
+                writer.write_all(line.as_bytes())?;
+                writer.queue_style(ResetColor)?;
+            }
+            Change::Insert(line) => {
+                writer.queue_style(SetForegroundColor(Color::Green))?;
+                writer.write_all(b"+ ")?;
+                writer.write_all(line.as_bytes())?;
+                writer.queue_style(ResetColor)?;
+            }
+        }
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines() {
+        use Change::*;
+
+        assert_eq!(diff::<u8>(&[], &[]), []);
+        assert_eq!(diff(&[1, 2, 3], &[1, 2, 3]), [Equal(1), Equal(2), Equal(3)]);
+        assert_eq!(diff(&[1, 2, 3], &[1, 3]), [Equal(1), Delete(2), Equal(3)]);
+        assert_eq!(diff(&[1, 3], &[1, 2, 3]), [Equal(1), Insert(2), Equal(3)]);
+        assert_eq!(
+            diff(&[1, 2, 3], &[1, 4, 3]),
+            [Equal(1), Delete(2), Insert(4), Equal(3)],
+        );
+        assert_eq!(diff(&[], &[1]), [Insert(1)]);
+        assert_eq!(diff(&[1], &[]), [Delete(1)]);
+    }
+
+    #[test]
+    fn split_words() {
+        assert_eq!(words(""), Vec::<&str>::new());
+        assert_eq!(
+            words("    let x = foo(1);"),
+            [
+                "    ", "let", " ", "x", " ", "=", " ", "foo", "(", "1", ")", ";"
+            ],
+        );
+        assert_eq!(words("a_b::c"), ["a_b", ":", ":", "c"]);
+        assert_eq!(words("ä ö"), ["ä", " ", "ö"]);
+    }
+
+    #[test]
+    fn line_diff() {
+        let mut output = Vec::new();
+        write_line_diff(&mut output, "a\nb\nc", "a\nx\nc").unwrap();
+
+        // Styling is enabled by default.
+        let output = String::from_utf8(output).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "  a");
+        assert!(lines[1].contains("- b"));
+        assert!(lines[2].contains("+ x"));
+        assert_eq!(lines[3], "  c");
+    }
+}
@@ -95,6 +95,18 @@ impl EmbeddedFiles {
         Ok(())
     }
 
+    /// The original content of an exercise file.
+    #[inline]
+    pub fn exercise_file(&self, exercise_ind: usize) -> &'static [u8] {
+        self.exercise_files[exercise_ind].exercise
+    }
+
+    /// The content of an exercise's solution file.
+    #[inline]
+    pub fn solution_file(&self, exercise_ind: usize) -> &'static [u8] {
+        self.exercise_files[exercise_ind].solution
+    }
+
     pub fn write_exercise_to_disk(&self, exercise_ind: usize, path: &str) -> Result<()> {
         let exercise_files = &self.exercise_files[exercise_ind];
         let dir = &self.exercise_dirs[exercise_files.dir_ind];
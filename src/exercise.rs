@@ -1,22 +1,27 @@
-use anyhow::Result;
-use crossterm::{
-    QueueableCommand,
-    style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
+use anyhow::{Context, Result};
+use crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, StdoutLock, Write},
+    path::Path,
+    str,
 };
-use std::io::{self, StdoutLock, Write};
 
 use crate::{
     cmd::CmdRunner,
-    term::{self, CountedWrite, terminal_file_link, write_ansi},
+    diff::write_line_diff,
+    term::{self, CountedWrite, QueueStyle, terminal_file_link, write_ansi},
 };
 
 /// The initial capacity of the output buffer.
 pub const OUTPUT_CAPACITY: usize = 1 << 14;
 
 pub fn solution_link_line(stdout: &mut StdoutLock, solution_path: &str) -> io::Result<()> {
-    stdout.queue(SetAttribute(Attribute::Bold))?;
+    stdout.queue_style(SetAttribute(Attribute::Bold))?;
     stdout.write_all(b"Solution")?;
-    stdout.queue(ResetColor)?;
+    stdout.queue_style(ResetColor)?;
     stdout.write_all(b" for comparison: ")?;
     if let Some(canonical_path) = term::canonicalize(solution_path) {
         terminal_file_link(stdout, solution_path, &canonical_path, Color::Cyan)?;
@@ -26,13 +31,51 @@ pub fn solution_link_line(stdout: &mut StdoutLock, solution_path: &str) -> io::R
     stdout.write_all(b"\n")
 }
 
+/// Explain why a working exercise isn't done yet in the teach mode.
+pub fn hint_required_line(writer: &mut impl Write, name: &str) -> io::Result<()> {
+    writer
+        .write_all(b"The exercise works, but the teach mode requires viewing its hint first: ")?;
+    writeln!(writer, "`rustlings hint {name}`")
+}
+
+/// Why an exercise isn't done yet.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PendingReason {
+    CompilationFailed,
+    TestsFailed,
+    /// Clippy reported lints (all lints with `strict_clippy`).
+    ClippyFailed,
+    /// The binary exited with a nonzero exit code.
+    RunFailed,
+    /// The output of the binary doesn't match the `expected_output`.
+    OutputMismatch,
+    /// The benchmark of the exercise took longer than `max_ns`.
+    TooSlow,
+    /// The source file contains tabs or trailing whitespace (only with `style_check`).
+    StyleViolations,
+    /// The source file contains one of the `forbidden` patterns.
+    ForbiddenPattern,
+    /// The exercise works, but its hint wasn't viewed yet (only in the teach mode).
+    HintNotViewed,
+}
+
+/// The prefix of the line in which a benchmark exercise reports its measured time.
+pub const BENCH_NS_PREFIX: &str = "bench_ns: ";
+
 // Run an exercise binary and append its output to the `output` buffer.
 // Compilation must be done before calling this method.
+// If `expected_output` is given, the output of the binary must match it (ignoring the whitespace
+// at the start and end).
+// The binary must exit with `expected_exit_code`.
+// `env` is only set for this run of the binary.
 fn run_bin(
     bin_name: &str,
+    expected_output: Option<&str>,
+    expected_exit_code: i32,
+    env: &BTreeMap<String, String>,
     mut output: Option<&mut Vec<u8>>,
     cmd_runner: &CmdRunner,
-) -> Result<bool> {
+) -> Result<Option<PendingReason>> {
     if let Some(output) = output.as_deref_mut() {
         write_ansi(output, SetAttribute(Attribute::Underlined));
         output.extend_from_slice(b"Output");
@@ -40,24 +83,468 @@ fn run_bin(
         output.push(b'\n');
     }
 
-    let success = cmd_runner.run_debug_bin(bin_name, output.as_deref_mut())?;
+    let Some(expected_output) = expected_output else {
+        let success =
+            cmd_runner.run_debug_bin(bin_name, expected_exit_code, env, output.as_deref_mut())?;
+        if success {
+            return Ok(None);
+        }
+        if let Some(output) = output {
+            write_run_failure(output, expected_exit_code);
+        }
+
+        return Ok(Some(PendingReason::RunFailed));
+    };
+
+    // The output is needed for the comparison even if it isn't shown.
+    let mut bin_output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let success =
+        cmd_runner.run_debug_bin(bin_name, expected_exit_code, env, Some(&mut bin_output))?;
+    let bin_output = String::from_utf8_lossy(&bin_output);
+    let output_matches = bin_output.trim() == expected_output.trim();
+
+    if let Some(output) = output {
+        output.extend_from_slice(bin_output.as_bytes());
+
+        if !success {
+            write_run_failure(output, expected_exit_code);
+        } else if !output_matches {
+            write_ansi(output, SetAttribute(Attribute::Bold));
+            write_ansi(output, SetForegroundColor(Color::Red));
+            output.extend_from_slice(b"The output doesn't match the expected output");
+            write_ansi(output, ResetColor);
+            output.extend_from_slice(b" (- expected, + actual)\n");
+            // Writing to a `Vec` can't fail.
+            let _ = write_line_diff(output, expected_output.trim(), bin_output.trim());
+        }
+    }
+
+    if !success {
+        Ok(Some(PendingReason::RunFailed))
+    } else if !output_matches {
+        Ok(Some(PendingReason::OutputMismatch))
+    } else {
+        Ok(None)
+    }
+}
+
+// Build the exercise binary with `--release`, run it and compare the time in nanoseconds which it
+// reports in a line starting with `BENCH_NS_PREFIX` with the maximum.
+// The measured time is appended to the `output` buffer.
+fn run_bench(
+    bin_name: &str,
+    max_ns: u64,
+    env: &BTreeMap<String, String>,
+    mut output: Option<&mut Vec<u8>>,
+    cmd_runner: &CmdRunner,
+) -> Result<Option<PendingReason>> {
+    let mut build_cmd = cmd_runner.cargo("build", bin_name, output.as_deref_mut());
+    build_cmd.args(["--release"]);
+    if !build_cmd.run("cargo build --release …")? {
+        return Ok(Some(PendingReason::CompilationFailed));
+    }
+
+    // The output is needed to read the measured time even if it isn't shown.
+    let mut bin_output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let success = cmd_runner.run_release_bin(bin_name, env, Some(&mut bin_output))?;
+    let bin_output = String::from_utf8_lossy(&bin_output);
+    // The last reported time counts.
+    let measured_ns = bin_output.lines().rev().find_map(|line| {
+        line.strip_prefix(BENCH_NS_PREFIX)
+            .and_then(|ns| ns.trim().parse::<u64>().ok())
+    });
 
     if let Some(output) = output {
+        write_ansi(output, SetAttribute(Attribute::Underlined));
+        output.extend_from_slice(b"Output");
+        write_ansi(output, ResetColor);
+        output.push(b'\n');
+        output.extend_from_slice(bin_output.as_bytes());
+
         if !success {
-            // This output is important to show the user that something went wrong.
-            // Otherwise, calling something like `exit(1)` in an exercise without further output
-            // leaves the user confused about why the exercise isn't done yet.
+            write_run_failure(output, 0);
+        } else if let Some(measured_ns) = measured_ns {
+            let color = if measured_ns <= max_ns {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            write_ansi(output, SetForegroundColor(color));
+            // Writing to a `Vec` can't fail.
+            let _ = write!(
+                output,
+                "Measured time: {measured_ns} ns (maximum: {max_ns} ns)",
+            );
+            write_ansi(output, ResetColor);
+            output.push(b'\n');
+        } else {
             write_ansi(output, SetAttribute(Attribute::Bold));
             write_ansi(output, SetForegroundColor(Color::Red));
-            output.extend_from_slice(b"The exercise didn't run successfully (nonzero exit code)");
+            let _ = write!(
+                output,
+                "The exercise didn't report the measured time in a line `{BENCH_NS_PREFIX}<NANOSECONDS>`",
+            );
             write_ansi(output, ResetColor);
             output.push(b'\n');
         }
     }
 
+    match measured_ns {
+        Some(measured_ns) if success => {
+            if measured_ns <= max_ns {
+                Ok(None)
+            } else {
+                Ok(Some(PendingReason::TooSlow))
+            }
+        }
+        _ => Ok(Some(PendingReason::RunFailed)),
+    }
+}
+
+/// Read a source file for display and checks.
+/// Invalid UTF-8 is replaced instead of failing because the compiler reports it anyway.
+pub fn read_source_lossy(source_path: &str) -> Result<String> {
+    let source =
+        fs::read(source_path).with_context(|| format!("Failed to read the file {source_path}"))?;
+
+    Ok(match String::from_utf8(source) {
+        Ok(source) => source,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+// The number of the first line which contains invalid UTF-8.
+fn invalid_utf8_line(source: &[u8]) -> Option<usize> {
+    let valid_up_to = str::from_utf8(source).err()?.valid_up_to();
+    let line_ind = source[..valid_up_to]
+        .iter()
+        .filter(|&&byte| byte == b'\n')
+        .count();
+    Some(line_ind + 1)
+}
+
+// Explain a compilation failure because of a source file which isn't valid UTF-8.
+fn write_invalid_utf8_note(source_path: &str, output: &mut Vec<u8>) {
+    // The compiler already reports a file which can't be read.
+    let Some(line) = fs::read(source_path)
+        .ok()
+        .and_then(|source| invalid_utf8_line(&source))
+    else {
+        return;
+    };
+
+    write_ansi(output, SetAttribute(Attribute::Bold));
+    write_ansi(output, SetForegroundColor(Color::Red));
+    output.extend_from_slice(b"The file isn't valid UTF-8");
+    write_ansi(output, ResetColor);
+    // Writing to a `Vec` can't fail.
+    let _ = writeln!(
+        output,
+        "\n{source_path}:{line}: invalid bytes. Save the file with the UTF-8 encoding",
+    );
+}
+
+// Check that the source file doesn't contain tabs or trailing whitespace.
+// The violations with their line numbers are appended to the `output` buffer.
+// Returns `true` if there are no violations.
+fn check_style(source_path: &str, mut output: Option<&mut Vec<u8>>) -> Result<bool> {
+    let source = read_source_lossy(source_path)?;
+
+    let mut success = true;
+    for (line_ind, line) in source.lines().enumerate() {
+        for (violated, violation) in [
+            (line.contains('\t'), "tab instead of spaces"),
+            (line.ends_with(char::is_whitespace), "trailing whitespace"),
+        ] {
+            if !violated {
+                continue;
+            }
+
+            if let Some(output) = output.as_deref_mut() {
+                if success {
+                    write_ansi(output, SetAttribute(Attribute::Bold));
+                    write_ansi(output, SetForegroundColor(Color::Red));
+                    output.extend_from_slice(b"The style check failed");
+                    write_ansi(output, ResetColor);
+                    output.push(b'\n');
+                }
+
+                // Writing to a `Vec` can't fail.
+                let _ = writeln!(output, "{source_path}:{}: {violation}", line_ind + 1);
+            }
+            success = false;
+        }
+    }
+
+    Ok(success)
+}
+
+// Check that the source file doesn't contain any of the `forbidden` patterns.
+// The found patterns with their line numbers are appended to the `output` buffer.
+// Returns `true` if none of the patterns is found.
+fn check_forbidden(
+    source_path: &str,
+    forbidden: &[impl AsRef<str>],
+    mut output: Option<&mut Vec<u8>>,
+) -> Result<bool> {
+    let source = read_source_lossy(source_path)?;
+
+    let mut success = true;
+    for (line_ind, line) in source.lines().enumerate() {
+        for pattern in forbidden {
+            let pattern = pattern.as_ref();
+            if !line.contains(pattern) {
+                continue;
+            }
+
+            if let Some(output) = output.as_deref_mut() {
+                if success {
+                    write_ansi(output, SetAttribute(Attribute::Bold));
+                    write_ansi(output, SetForegroundColor(Color::Red));
+                    output.extend_from_slice(b"Forbidden pattern found");
+                    write_ansi(output, ResetColor);
+                    output.push(b'\n');
+                }
+
+                // Writing to a `Vec` can't fail.
+                let _ = writeln!(output, "{source_path}:{}: `{pattern}`", line_ind + 1);
+            }
+            success = false;
+        }
+    }
+
     Ok(success)
 }
 
+fn write_run_failure(output: &mut Vec<u8>, expected_exit_code: i32) {
+    // This output is important to show the user that something went wrong.
+    // Otherwise, calling something like `exit(1)` in an exercise without further output
+    // leaves the user confused about why the exercise isn't done yet.
+    write_ansi(output, SetAttribute(Attribute::Bold));
+    write_ansi(output, SetForegroundColor(Color::Red));
+    if expected_exit_code == 0 {
+        output.extend_from_slice(b"The exercise didn't run successfully (nonzero exit code)");
+    } else {
+        // Writing to a `Vec` can't fail.
+        let _ = write!(
+            output,
+            "The exercise didn't exit with the expected exit code {expected_exit_code}",
+        );
+    }
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+}
+
+/// Remove ANSI escape sequences like colors from the output of a command.
+pub fn strip_ansi(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+
+        // Skip a control sequence like `ESC[1;96m` until its final byte.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    stripped
+}
+
+// Parses parts of the JSON messages of `cargo build --message-format json…`.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+// A diagnostic of the compiler like an error, a warning or a help message.
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    // For example `error`, `warning`, `note` or `help`.
+    level: String,
+    #[serde(default)]
+    spans: Vec<DiagnosticSpan>,
+    #[serde(default)]
+    children: Vec<Diagnostic>,
+    // The human-readable rendering. Only set for top-level diagnostics.
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticSpan {
+    // Relative to the directory of the manifest.
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+}
+
+impl Diagnostic {
+    // The primary span which the diagnostic is about.
+    fn primary_span(&self) -> Option<&DiagnosticSpan> {
+        self.spans.iter().find(|span| span.is_primary)
+    }
+}
+
+// Replace the JSON messages of Cargo in the output with the human-readable rendering of the
+// compiler diagnostics in them. Other lines like the errors of Cargo itself are kept.
+// Returns the parsed diagnostics.
+fn render_diagnostics(output: &mut Vec<u8>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut rendered_output = Vec::with_capacity(output.len());
+
+    for line in output.split_inclusive(|c| *c == b'\n') {
+        let Ok(message) = serde_json::from_slice::<CargoMessage>(line) else {
+            rendered_output.extend_from_slice(line);
+            continue;
+        };
+
+        // Other messages like `compiler-artifact` and `build-finished` are dropped.
+        if message.reason != "compiler-message" {
+            continue;
+        }
+        let Some(diagnostic) = message.message else {
+            continue;
+        };
+
+        if let Some(rendered) = &diagnostic.rendered {
+            rendered_output.extend_from_slice(rendered.as_bytes());
+        }
+        diagnostics.push(diagnostic);
+    }
+
+    *output = rendered_output;
+    diagnostics
+}
+
+// The help messages of the compiler without duplicates.
+// They are prefixed by the location of their diagnostic.
+fn compiler_suggestions(diagnostics: &[Diagnostic]) -> Vec<String> {
+    let mut suggestions = Vec::new();
+
+    for diagnostic in diagnostics {
+        let location = diagnostic.primary_span().map(|span| {
+            format!(
+                "{}:{}:{}",
+                span.file_name, span.line_start, span.column_start,
+            )
+        });
+
+        for help in diagnostic
+            .children
+            .iter()
+            .filter(|child| child.level == "help")
+        {
+            let suggestion = match &location {
+                Some(location) => format!("{location}: {}", help.message),
+                None => help.message.clone(),
+            };
+            if !suggestions.contains(&suggestion) {
+                suggestions.push(suggestion);
+            }
+        }
+    }
+
+    suggestions
+}
+
+// Repeat the suggestions of the compiler after its output so that they aren't missed.
+fn write_compiler_suggestions(diagnostics: &[Diagnostic], output: &mut Vec<u8>) {
+    let suggestions = compiler_suggestions(diagnostics);
+    if suggestions.is_empty() {
+        return;
+    }
+
+    write_ansi(output, SetAttribute(Attribute::Underlined));
+    output.extend_from_slice(b"Suggestions from the compiler");
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+
+    for suggestion in suggestions {
+        output.extend_from_slice(b"- ");
+        write_ansi(output, SetAttribute(Attribute::Bold));
+        output.extend_from_slice(suggestion.as_bytes());
+        write_ansi(output, ResetColor);
+        output.push(b'\n');
+    }
+}
+
+// The number of lines shown before and after the line of the first compiler error.
+const ERROR_CONTEXT_LINES: usize = 2;
+
+// The line number of the first error which the compiler reported in the source file.
+// Warnings before it are ignored.
+fn first_error_line(diagnostics: &[Diagnostic], source_path: &str) -> Option<usize> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level.starts_with("error"))
+        .filter_map(Diagnostic::primary_span)
+        // Cargo prints the paths relative to the directory of the manifest.
+        // Whole path components are compared to not match `other_exercises/intro2.rs` for `exercises/intro2.rs`.
+        .find(|span| Path::new(&span.file_name).ends_with(source_path))
+        .map(|span| span.line_start)
+}
+
+// Show the source lines around the first compiler error with an arrow at its line.
+// Nothing is shown if no error line can be found in the compiler output.
+fn write_error_context(source_path: &str, diagnostics: &[Diagnostic], output: &mut Vec<u8>) {
+    let Some(error_line) = first_error_line(diagnostics, source_path) else {
+        return;
+    };
+    let Ok(source) = read_source_lossy(source_path) else {
+        return;
+    };
+
+    let first_line = error_line.saturating_sub(ERROR_CONTEXT_LINES).max(1);
+    let lines = source
+        .lines()
+        .zip(1..)
+        .skip(first_line - 1)
+        .take(error_line + ERROR_CONTEXT_LINES + 1 - first_line)
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return;
+    }
+    let number_width = (error_line + ERROR_CONTEXT_LINES).to_string().len();
+
+    write_ansi(output, SetAttribute(Attribute::Underlined));
+    // Writing to a `Vec` can't fail.
+    let _ = write!(output, "First error in {source_path}");
+    write_ansi(output, ResetColor);
+    output.push(b'\n');
+
+    for (line, line_number) in lines {
+        if line_number == error_line {
+            write_ansi(output, SetAttribute(Attribute::Bold));
+            write_ansi(output, SetForegroundColor(Color::Red));
+            let _ = writeln!(output, "> {line_number:>number_width$} | {line}");
+            write_ansi(output, ResetColor);
+        } else {
+            let _ = writeln!(output, "  {line_number:>number_width$} | {line}");
+        }
+    }
+}
+
+/// The result of checking an exercise.
+pub struct CheckResult {
+    /// The exercise compiled, its tests passed, Clippy didn't complain and it ran successfully.
+    pub success: bool,
+    /// Why the exercise isn't done yet. `None` on success.
+    pub pending_reason: Option<PendingReason>,
+    /// The merged output of all the commands which were run to check the exercise.
+    pub output: Vec<u8>,
+}
+
 /// See `info_file::ExerciseInfo`
 pub struct Exercise {
     pub dir: Option<&'static str>,
@@ -66,9 +553,27 @@ pub struct Exercise {
     pub path: &'static str,
     pub canonical_path: Option<String>,
     pub test: bool,
+    pub test_filter: Option<&'static str>,
     pub strict_clippy: bool,
+    pub expected_output: Option<&'static str>,
+    pub expected_exit_code: i32,
+    pub max_ns: Option<u64>,
+    pub style_check: bool,
+    pub tags: Vec<&'static str>,
+    pub forbidden: Vec<&'static str>,
+    pub multi_file: bool,
+    /// From 1 (easiest) to 5.
+    pub difficulty: Option<u8>,
+    pub estimated_minutes: Option<u16>,
+    /// Indices of the exercises which must be done before jumping to this one.
+    pub requires: Vec<usize>,
+    pub env: BTreeMap<String, String>,
     pub hint: &'static str,
+    /// The hint was viewed. Only tracked in the teach mode.
+    pub hint_viewed: bool,
     pub done: bool,
+    /// Skipped for now. It doesn't block moving on to the next exercises.
+    pub skipped: bool,
 }
 
 impl Exercise {
@@ -79,6 +584,18 @@ impl Exercise {
 
         writer.write_str(self.path)
     }
+
+    /// The exercise file doesn't exist (anymore).
+    /// Checked on every call because the file can be restored outside of Rustlings.
+    #[inline]
+    pub fn missing(&self) -> bool {
+        !Path::new(self.path).exists()
+    }
+
+    #[inline]
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
 }
 
 pub trait RunnableExercise {
@@ -86,24 +603,52 @@ pub trait RunnableExercise {
     fn dir(&self) -> Option<&str>;
     fn strict_clippy(&self) -> bool;
     fn test(&self) -> bool;
+    fn test_filter(&self) -> Option<&str>;
+    fn expected_output(&self) -> Option<&str>;
+    fn expected_exit_code(&self) -> i32;
+    fn max_ns(&self) -> Option<u64>;
+    fn style_check(&self) -> bool;
+    fn forbidden(&self) -> &[impl AsRef<str>];
+    fn multi_file(&self) -> bool;
+    fn env(&self) -> &BTreeMap<String, String>;
 
     // Compile, check and run the exercise or its solution (depending on `bin_name´).
+    // `source_path` is the file of the binary which is read for the style check and the forbidden patterns.
     // The output is written to the `output` buffer after clearing it.
-    fn run<const FORCE_STRICT_CLIPPY: bool>(
+    // Returns why the exercise is still pending or `None` if it is done.
+    fn run_checks<const FORCE_STRICT_CLIPPY: bool>(
         &self,
         bin_name: &str,
+        source_path: &str,
         mut output: Option<&mut Vec<u8>>,
         cmd_runner: &CmdRunner,
-    ) -> Result<bool> {
+    ) -> Result<Option<PendingReason>> {
         if let Some(output) = output.as_deref_mut() {
             output.clear();
         }
 
-        let build_success = cmd_runner
-            .cargo("build", bin_name, output.as_deref_mut())
-            .run("cargo build …")?;
+        // Done before compiling to show the violations even if the exercise doesn't compile yet.
+        if self.style_check() && !check_style(source_path, output.as_deref_mut())? {
+            return Ok(Some(PendingReason::StyleViolations));
+        }
+
+        let forbidden = self.forbidden();
+        if !forbidden.is_empty() && !check_forbidden(source_path, forbidden, output.as_deref_mut())?
+        {
+            return Ok(Some(PendingReason::ForbiddenPattern));
+        }
+
+        let mut build_cmd = cmd_runner.cargo("build", bin_name, output.as_deref_mut());
+        build_cmd.json_diagnostics();
+        let build_success = build_cmd.run("cargo build …")?;
         if !build_success {
-            return Ok(false);
+            if let Some(output) = output {
+                let diagnostics = render_diagnostics(output);
+                write_compiler_suggestions(&diagnostics, output);
+                write_error_context(source_path, &diagnostics, output);
+                write_invalid_utf8_note(source_path, output);
+            }
+            return Ok(Some(PendingReason::CompilationFailed));
         }
 
         // Discard the compiler output because it will be shown again by `cargo test` or Clippy.
@@ -113,14 +658,30 @@ pub trait RunnableExercise {
 
         if self.test() {
             let output_is_some = output.is_some();
+            let test_filter = self.test_filter();
             let mut test_cmd = cmd_runner.cargo("test", bin_name, output.as_deref_mut());
+            test_cmd.envs(self.env());
+            if output_is_some || test_filter.is_some() {
+                test_cmd.args(["--"]);
+            }
+            if let Some(test_filter) = test_filter {
+                test_cmd.args([test_filter]);
+            }
             if output_is_some {
-                test_cmd.args(["--", "--color", "always", "--format", "pretty"]);
+                let color = if term::styling() { "always" } else { "never" };
+                test_cmd.args(["--color", color, "--format", "pretty"]);
             }
             let test_success = test_cmd.run("cargo test …")?;
             if !test_success {
-                run_bin(bin_name, output, cmd_runner)?;
-                return Ok(false);
+                run_bin(
+                    bin_name,
+                    None,
+                    self.expected_exit_code(),
+                    self.env(),
+                    output,
+                    cmd_runner,
+                )?;
+                return Ok(Some(PendingReason::TestsFailed));
             }
 
             // Discard the compiler output because it will be shown again by Clippy.
@@ -139,16 +700,68 @@ pub trait RunnableExercise {
         }
 
         let clippy_success = clippy_cmd.run("cargo clippy …")?;
-        let run_success = run_bin(bin_name, output, cmd_runner)?;
+        if !clippy_success {
+            cmd_runner.ensure_clippy_installed(output.as_deref().map(Vec::as_slice))?;
+        }
+        let run_pending_reason = match self.max_ns() {
+            Some(max_ns) => run_bench(bin_name, max_ns, self.env(), output, cmd_runner)?,
+            None => run_bin(
+                bin_name,
+                self.expected_output(),
+                self.expected_exit_code(),
+                self.env(),
+                output,
+                cmd_runner,
+            )?,
+        };
 
-        Ok(clippy_success && run_success)
+        if clippy_success {
+            Ok(run_pending_reason)
+        } else {
+            Ok(Some(PendingReason::ClippyFailed))
+        }
+    }
+
+    // Like `run_checks` but only returns whether the exercise is done.
+    #[inline]
+    fn run<const FORCE_STRICT_CLIPPY: bool>(
+        &self,
+        bin_name: &str,
+        source_path: &str,
+        output: Option<&mut Vec<u8>>,
+        cmd_runner: &CmdRunner,
+    ) -> Result<bool> {
+        self.run_checks::<FORCE_STRICT_CLIPPY>(bin_name, source_path, output, cmd_runner)
+            .map(|pending_reason| pending_reason.is_none())
     }
 
     /// Compile, check and run the exercise.
     /// The output is written to the `output` buffer after clearing it.
     #[inline]
     fn run_exercise(&self, output: Option<&mut Vec<u8>>, cmd_runner: &CmdRunner) -> Result<bool> {
-        self.run::<false>(self.name(), output, cmd_runner)
+        self.run::<false>(self.name(), &self.exercise_path(), output, cmd_runner)
+    }
+
+    /// Like `run_exercise`, but returns why the exercise is still pending or `None` if it is done.
+    #[inline]
+    fn exercise_pending_reason(
+        &self,
+        output: Option<&mut Vec<u8>>,
+        cmd_runner: &CmdRunner,
+    ) -> Result<Option<PendingReason>> {
+        self.run_checks::<false>(self.name(), &self.exercise_path(), output, cmd_runner)
+    }
+
+    /// Compile, check and run the exercise without printing anything.
+    fn check_exercise(&self, cmd_runner: &CmdRunner) -> Result<CheckResult> {
+        let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+        let pending_reason = self.exercise_pending_reason(Some(&mut output), cmd_runner)?;
+
+        Ok(CheckResult {
+            success: pending_reason.is_none(),
+            pending_reason,
+            output,
+        })
     }
 
     /// Compile, check and run the exercise's solution.
@@ -159,33 +772,44 @@ pub trait RunnableExercise {
         bin_name.push_str(name);
         bin_name.push_str("_sol");
 
-        self.run::<true>(&bin_name, output, cmd_runner)
+        self.run::<true>(&bin_name, &self.sol_path(), output, cmd_runner)
+    }
+
+    fn exercise_path(&self) -> String {
+        file_path("exercises/", self.dir(), self.name(), self.multi_file())
     }
 
     fn sol_path(&self) -> String {
-        let name = self.name();
+        file_path("solutions/", self.dir(), self.name(), self.multi_file())
+    }
+}
 
-        let mut path = if let Some(dir) = self.dir() {
-            // 14 = 10 + 1 + 3
-            // solutions/ + / + .rs
-            let mut path = String::with_capacity(14 + dir.len() + name.len());
-            path.push_str("solutions/");
-            path.push_str(dir);
-            path.push('/');
-            path
-        } else {
-            // 13 = 10 + 3
-            // solutions/ + .rs
-            let mut path = String::with_capacity(13 + name.len());
-            path.push_str("solutions/");
-            path
-        };
+// The path of an exercise or solution file in the directory `root_dir` which ends with a slash.
+// The file of a multi-file exercise is `main.rs` in the directory with the exercise's name.
+fn file_path(root_dir: &str, dir: Option<&str>, name: &str, multi_file: bool) -> String {
+    let mut path = if let Some(dir) = dir {
+        // 4 = 1 + 3
+        // / + .rs
+        let mut path = String::with_capacity(root_dir.len() + dir.len() + 4 + name.len());
+        path.push_str(root_dir);
+        path.push_str(dir);
+        path.push('/');
+        path
+    } else {
+        // 3 = .rs
+        let mut path = String::with_capacity(root_dir.len() + 3 + name.len());
+        path.push_str(root_dir);
+        path
+    };
 
-        path.push_str(name);
+    path.push_str(name);
+    if multi_file {
+        path.push_str("/main.rs");
+    } else {
         path.push_str(".rs");
-
-        path
     }
+
+    path
 }
 
 impl RunnableExercise for Exercise {
@@ -208,4 +832,198 @@ impl RunnableExercise for Exercise {
     fn test(&self) -> bool {
         self.test
     }
+
+    #[inline]
+    fn test_filter(&self) -> Option<&str> {
+        self.test_filter
+    }
+
+    #[inline]
+    fn expected_output(&self) -> Option<&str> {
+        self.expected_output
+    }
+
+    #[inline]
+    fn expected_exit_code(&self) -> i32 {
+        self.expected_exit_code
+    }
+
+    #[inline]
+    fn max_ns(&self) -> Option<u64> {
+        self.max_ns
+    }
+
+    #[inline]
+    fn style_check(&self) -> bool {
+        self.style_check
+    }
+
+    #[inline]
+    fn forbidden(&self) -> &[impl AsRef<str>] {
+        &self.forbidden
+    }
+
+    #[inline]
+    fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    #[inline]
+    fn multi_file(&self) -> bool {
+        self.multi_file
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A JSON message of Cargo with a compiler diagnostic.
+    fn diagnostic_message(
+        level: &str,
+        message: &str,
+        location: Option<(&str, usize, usize)>,
+        helps: &[&str],
+    ) -> String {
+        let spans = location.map_or_else(Vec::new, |(file_name, line_start, column_start)| {
+            vec![serde_json::json!({
+                "file_name": file_name,
+                "line_start": line_start,
+                "column_start": column_start,
+                "is_primary": true,
+            })]
+        });
+        let children = helps
+            .iter()
+            .map(|help| serde_json::json!({ "message": help, "level": "help" }))
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "reason": "compiler-message",
+            "message": {
+                "message": message,
+                "level": level,
+                "spans": spans,
+                "children": children,
+                "rendered": format!("{level}: {message}\n"),
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn rendering_diagnostics() {
+        let mut output = format!(
+            "{}\nerror: could not compile `exercises`\n{{\"reason\":\"build-finished\",\"success\":false}}\n",
+            diagnostic_message("error", "expected pattern", None, &[]),
+        )
+        .into_bytes();
+
+        let diagnostics = render_diagnostics(&mut output);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            output,
+            b"error: expected pattern\nerror: could not compile `exercises`\n",
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let mut output = [
+            diagnostic_message(
+                "error",
+                "cannot find value `y` in this scope",
+                Some(("exercises/intro2.rs", 5, 20)),
+                &["a local variable with a similar name exists"],
+            ),
+            diagnostic_message(
+                "error",
+                "mismatched types",
+                Some(("exercises/intro2.rs", 4, 21)),
+                &[
+                    "try using a conversion method",
+                    "try using a conversion method",
+                ],
+            ),
+            diagnostic_message("error", "aborting due to 2 previous errors", None, &[]),
+        ]
+        .join("\n")
+        .into_bytes();
+        let diagnostics = render_diagnostics(&mut output);
+
+        assert_eq!(
+            compiler_suggestions(&diagnostics),
+            [
+                "exercises/intro2.rs:5:20: a local variable with a similar name exists",
+                "exercises/intro2.rs:4:21: try using a conversion method",
+            ],
+        );
+        assert!(compiler_suggestions(&diagnostics[2..]).is_empty());
+    }
+
+    #[test]
+    fn error_line() {
+        let mut output = [
+            diagnostic_message(
+                "warning",
+                "unused variable: `x`",
+                Some(("../exercises/intro2.rs", 2, 9)),
+                &[],
+            ),
+            diagnostic_message(
+                "error",
+                "cannot find value `y` in this scope",
+                Some(("src/lib.rs", 1, 1)),
+                &[],
+            ),
+            diagnostic_message(
+                "error",
+                "cannot find value `y` in this scope",
+                Some(("../other_exercises/intro2.rs", 3, 1)),
+                &[],
+            ),
+            diagnostic_message(
+                "error",
+                "cannot find value `y` in this scope",
+                Some(("../exercises/intro2.rs", 5, 20)),
+                &[],
+            ),
+            diagnostic_message(
+                "error",
+                "mismatched types",
+                Some(("../exercises/intro2.rs", 4, 21)),
+                &[],
+            ),
+        ]
+        .join("\n")
+        .into_bytes();
+        let diagnostics = render_diagnostics(&mut output);
+
+        assert_eq!(
+            first_error_line(&diagnostics, "exercises/intro2.rs"),
+            Some(5)
+        );
+        assert_eq!(first_error_line(&diagnostics, "exercises/intro3.rs"), None);
+        assert_eq!(first_error_line(&[], "exercises/intro2.rs"), None);
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(invalid_utf8_line(b"fn main() {}\n"), None);
+        assert_eq!(invalid_utf8_line(b"fn main() {\n    \xff\n}\n"), Some(2));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let source_path = dir.path().join("invalid.rs");
+        fs::write(&source_path, b"fn main() {\t\n    // \xc3\x28 \n}\n").unwrap();
+        let source_path = source_path.to_str().unwrap();
+
+        assert!(read_source_lossy(source_path).unwrap().contains('\u{FFFD}'));
+        let mut output = Vec::new();
+        assert!(!check_style(source_path, Some(&mut output)).unwrap());
+        assert!(String::from_utf8_lossy(&output).contains(":2: trailing whitespace"));
+
+        output.clear();
+        write_invalid_utf8_note(source_path, &mut output);
+        assert!(String::from_utf8_lossy(&output).contains(":2: invalid bytes"));
+    }
 }
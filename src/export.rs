@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    fmt::Write as _,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::app_state::AppState;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Md,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Chapter<'a> {
+    name: &'a str,
+    done: u16,
+    total: u16,
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    done: u16,
+    total: u16,
+    percentage: f64,
+    exported_at: String,
+    chapters: Vec<Chapter<'a>>,
+    completed_exercises: Vec<&'a str>,
+}
+
+/// Format seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+pub fn format_utc_timestamp(secs: u64) -> String {
+    // Civil from days: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (secs / 86400) as i64;
+    let secs_of_day = secs % 86400;
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
+}
+
+/// The current time as an RFC 3339 UTC timestamp.
+pub fn now_utc_timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    format_utc_timestamp(secs)
+}
+
+fn summary(app_state: &AppState) -> Summary<'_> {
+    let mut chapters: Vec<Chapter> = Vec::with_capacity(32);
+    let mut completed_exercises = Vec::with_capacity(app_state.exercises().len());
+
+    for exercise in app_state.exercises() {
+        let chapter_name = exercise.dir.unwrap_or("-");
+        // The chapter is often the last one inserted.
+        let chapter = match chapters
+            .iter()
+            .rposition(|chapter| chapter.name == chapter_name)
+        {
+            Some(ind) => &mut chapters[ind],
+            None => {
+                chapters.push(Chapter {
+                    name: chapter_name,
+                    done: 0,
+                    total: 0,
+                });
+                chapters.last_mut().unwrap()
+            }
+        };
+
+        chapter.total += 1;
+        if exercise.done {
+            chapter.done += 1;
+            completed_exercises.push(exercise.name);
+        }
+    }
+
+    Summary {
+        done: app_state.n_done(),
+        total: app_state.exercises().len() as u16,
+        percentage: app_state.completion_percentage(),
+        exported_at: now_utc_timestamp(),
+        chapters,
+        completed_exercises,
+    }
+}
+
+fn markdown(summary: &Summary) -> String {
+    let mut md = String::with_capacity(4096);
+
+    // Writing to a `String` can't fail.
+    let _ = writeln!(
+        md,
+        "# Rustlings Summary\n\nCompleted {}/{} exercises ({:.1}%)\n\nExported at {}\n",
+        summary.done, summary.total, summary.percentage, summary.exported_at,
+    );
+
+    md.push_str("## Chapters\n\n| Chapter | Done |\n| ------- | ---- |\n");
+    for chapter in &summary.chapters {
+        let _ = writeln!(
+            md,
+            "| {} | {}/{} |",
+            chapter.name, chapter.done, chapter.total
+        );
+    }
+
+    md.push_str("\n## Completed Exercises\n\n");
+    for exercise_name in &summary.completed_exercises {
+        let _ = writeln!(md, "- {exercise_name}");
+    }
+
+    md
+}
+
+pub fn export(app_state: &AppState, format: ExportFormat, output: &Path) -> Result<()> {
+    let summary = summary(app_state);
+
+    let content = match format {
+        ExportFormat::Md => markdown(&summary),
+        ExportFormat::Json => serde_json::to_string_pretty(&summary)
+            .context("Failed to serialize the summary to JSON")?,
+    };
+
+    fs::write(output, content)
+        .with_context(|| format!("Failed to write the file {}", output.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_timestamp() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1_792_152_245), "2026-10-16T12:04:05Z");
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+
+use crate::{app_state::AppState, exercise::Exercise};
+
+// The fill colors of the nodes by the exercise state.
+fn fill_color(exercise: &Exercise) -> &'static str {
+    if exercise.done {
+        "palegreen"
+    } else if exercise.skipped {
+        "lightblue"
+    } else {
+        "khaki"
+    }
+}
+
+// A quoted DOT identifier.
+fn quoted(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('"');
+    for c in name.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn write_dot(writer: &mut impl Write, exercises: &[Exercise]) -> io::Result<()> {
+    writer.write_all(b"digraph rustlings {\n    node [shape=box, style=filled];\n")?;
+
+    for exercise in exercises {
+        writeln!(
+            writer,
+            "    {} [fillcolor={}];",
+            quoted(exercise.name),
+            fill_color(exercise),
+        )?;
+    }
+
+    // Without any requirements, the exercises depend on each other in their order.
+    if exercises
+        .iter()
+        .all(|exercise| exercise.requires.is_empty())
+    {
+        for pair in exercises.windows(2) {
+            writeln!(
+                writer,
+                "    {} -> {};",
+                quoted(pair[0].name),
+                quoted(pair[1].name),
+            )?;
+        }
+    } else {
+        for exercise in exercises {
+            for &required_ind in &exercise.requires {
+                writeln!(
+                    writer,
+                    "    {} -> {};",
+                    quoted(exercises[required_ind].name),
+                    quoted(exercise.name),
+                )?;
+            }
+        }
+    }
+
+    writer.write_all(b"}\n")
+}
+
+/// Write the graph of the exercise requirements in the DOT format of Graphviz to stdout.
+pub fn graph(app_state: &AppState) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    write_dot(&mut stdout, app_state.exercises())
+        .and_then(|()| stdout.flush())
+        .context("Failed to write the graph to stdout")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_names() {
+        assert_eq!(quoted("intro1"), r#""intro1""#);
+        assert_eq!(quoted(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
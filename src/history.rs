@@ -0,0 +1,94 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+};
+
+use crate::export::now_utc_timestamp;
+
+/// One JSON line per exercise which was done.
+const HISTORY_FILE_NAME: &str = ".rustlings-history.jsonl";
+
+#[derive(Serialize, Deserialize)]
+struct Entry<'a> {
+    exercise: &'a str,
+    /// RFC 3339 UTC timestamp.
+    done_at: &'a str,
+}
+
+fn append(exercise_name: &str) -> Result<()> {
+    let done_at = now_utc_timestamp();
+    let mut line = serde_json::to_vec(&Entry {
+        exercise: exercise_name,
+        done_at: &done_at,
+    })
+    .context("Failed to serialize the history entry")?;
+    line.push(b'\n');
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_NAME)
+        .and_then(|mut file| file.write_all(&line))
+        .with_context(|| format!("Failed to append to the history file {HISTORY_FILE_NAME}"))
+}
+
+/// Record in the history file that an exercise was just done.
+/// Failures are only logged because the history is optional (e.g. on a read-only file system).
+pub fn record_done(exercise_name: &str) {
+    if let Err(e) = append(exercise_name) {
+        log::warn!("{e:#}");
+    }
+}
+
+// Count the done exercises per UTC day (`YYYY-MM-DD`). Invalid lines are ignored.
+fn count_done_per_day(history: &str) -> BTreeMap<String, usize> {
+    let mut done_per_day = BTreeMap::new();
+    for line in history.lines() {
+        let Ok(entry) = serde_json::from_str::<Entry>(line) else {
+            continue;
+        };
+        let Some(day) = entry.done_at.get(..10) else {
+            continue;
+        };
+
+        *done_per_day.entry(day.to_string()).or_default() += 1;
+    }
+
+    done_per_day
+}
+
+/// The number of done exercises per UTC day (`YYYY-MM-DD`) according to the history file.
+pub fn done_per_day() -> Result<BTreeMap<String, usize>> {
+    let history = match fs::read_to_string(HISTORY_FILE_NAME) {
+        Ok(history) => history,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => {
+            return Err(e)
+                .with_context(|| format!("Failed to read the history file {HISTORY_FILE_NAME}"));
+        }
+    };
+
+    Ok(count_done_per_day(&history))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_per_day() {
+        let history = r#"{"exercise":"intro1","done_at":"2026-10-15T09:00:00Z"}
+{"exercise":"intro2","done_at":"2026-10-15T23:59:59Z"}
+invalid
+{"exercise":"variables1","done_at":"2026-10-16T00:00:00Z"}
+"#;
+
+        assert_eq!(
+            count_done_per_day(history).into_iter().collect::<Vec<_>>(),
+            [("2026-10-15".to_string(), 2), ("2026-10-16".to_string(), 1)],
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use std::{collections::HashMap, env, sync::OnceLock};
+
+const DEFAULT_LANG: &str = "en";
+
+/// The embedded locale files. The first one is the default.
+const LOCALES: &[(&str, &str)] = &[
+    (DEFAULT_LANG, include_str!("locales/en.toml")),
+    ("de", include_str!("locales/de.toml")),
+];
+
+type Strings = HashMap<String, String>;
+
+struct Locale {
+    selected: Option<Strings>,
+    default: Strings,
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+fn parse(lang: &str, content: &str) -> Result<Strings> {
+    toml_edit::de::from_str(content)
+        .with_context(|| format!("Failed to parse the locale file of the language `{lang}`"))
+}
+
+/// Load the locale selected by the `RUSTLINGS_LANG` environment variable (default `en`).
+/// Unknown languages fall back to English.
+pub fn init() -> Result<()> {
+    let default = parse(DEFAULT_LANG, LOCALES[0].1)?;
+
+    let selected = match env::var("RUSTLINGS_LANG") {
+        Ok(lang) if lang != DEFAULT_LANG => LOCALES
+            .iter()
+            .find(|(name, _)| *name == lang)
+            .map(|(name, content)| parse(name, content))
+            .transpose()?,
+        _ => None,
+    };
+
+    // Ignore the error if it is already initialized.
+    let _ = LOCALE.set(Locale { selected, default });
+
+    Ok(())
+}
+
+/// Look up the string of the given key in the selected locale.
+/// Falls back to English for keys missing in the selected locale.
+pub fn tr(key: &str) -> &str {
+    let Some(locale) = LOCALE.get() else {
+        return key;
+    };
+
+    locale
+        .selected
+        .as_ref()
+        .and_then(|strings| strings.get(key))
+        .or_else(|| locale.default.get(key))
+        .map_or(key, String::as_str)
+}
+
+/// Like `tr`, but replaces the placeholders `{name}` with their values.
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    let mut s = tr(key).to_string();
+    for (name, value) in args {
+        s = s.replace(&format!("{{{name}}}"), value);
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(s: &str) -> Vec<&str> {
+        let mut placeholders = s
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect::<Vec<_>>();
+        placeholders.sort_unstable();
+        placeholders
+    }
+
+    #[test]
+    fn locales_are_consistent() {
+        let default = parse(DEFAULT_LANG, LOCALES[0].1).unwrap();
+
+        for (lang, content) in &LOCALES[1..] {
+            for (key, value) in parse(lang, content).unwrap() {
+                let default_value = default
+                    .get(&key)
+                    .unwrap_or_else(|| panic!("Unknown key `{key}` in the locale `{lang}`"));
+                assert_eq!(
+                    placeholders(&value),
+                    placeholders(default_value),
+                    "Placeholders of the key `{key}` in the locale `{lang}`",
+                );
+            }
+        }
+    }
+}
@@ -1,9 +1,19 @@
 use anyhow::{Context, Error, Result, bail};
 use serde::Deserialize;
-use std::{fs, io::ErrorKind};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::ErrorKind,
+};
 
 use crate::{embedded::EMBEDDED_FILES, exercise::RunnableExercise};
 
+/// The latest supported format version of the `info.toml` file.
+pub const CURRENT_FORMAT_VERSION: u8 = 1;
+
+/// Lists the names of third-party exercises to hide (one per line).
+pub const IGNORE_FILE_NAME: &str = ".rustlings-ignore";
+
 /// Deserialized from the `info.toml` file.
 #[derive(Deserialize)]
 pub struct ExerciseInfo {
@@ -17,6 +27,49 @@ pub struct ExerciseInfo {
     /// Deny all Clippy warnings.
     #[serde(default)]
     pub strict_clippy: bool,
+    /// Only run the tests whose names contain this filter.
+    #[serde(default)]
+    pub test_filter: Option<String>,
+    /// The output which the exercise must print when it is run.
+    /// Whitespace at the start and end is ignored.
+    #[serde(default)]
+    pub expected_output: Option<String>,
+    /// The exit code which the exercise must exit with when it is run.
+    #[serde(default)]
+    pub expected_exit_code: i32,
+    /// The exercise is a benchmark. It is built with `--release` and must report a time in
+    /// nanoseconds which isn't larger than this maximum.
+    #[serde(default)]
+    pub max_ns: Option<u64>,
+    /// Fail if the exercise file contains tabs or trailing whitespace before compiling it.
+    #[serde(default)]
+    pub style_check: bool,
+    /// Tags like `ownership` to filter exercises by.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Fail if the exercise file contains one of these patterns (e.g. a hardcoded output).
+    #[serde(default)]
+    pub forbidden: Vec<String>,
+    /// The exercise is a directory with the exercise's name containing `main.rs` and further
+    /// module files. Only `main.rs` is read for the checks of the exercise file.
+    #[serde(default)]
+    pub multi_file: bool,
+    /// The difficulty from 1 (easiest) to 5 shown in the list.
+    #[serde(default)]
+    pub difficulty: Option<u8>,
+    /// The estimated time to solve the exercise in minutes shown in the list.
+    #[serde(default)]
+    pub estimated_minutes: Option<u16>,
+    /// Names of the exercises which must be done before jumping to this one.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Environment variables which are set when running the exercise and its tests.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// The Rust edition of the exercise and its solution (e.g. `2018`) if it differs from the
+    /// edition of the package in `Cargo.toml`.
+    #[serde(default)]
+    pub edition: Option<String>,
     /// The exercise's hint to be shown to the user on request.
     pub hint: String,
     /// The exercise is already solved. Ignore it when checking that all exercises are unsolved.
@@ -48,7 +101,11 @@ impl ExerciseInfo {
         };
 
         path.push_str(&self.name);
-        path.push_str(".rs");
+        if self.multi_file {
+            path.push_str("/main.rs");
+        } else {
+            path.push_str(".rs");
+        }
 
         path
     }
@@ -74,6 +131,46 @@ impl RunnableExercise for ExerciseInfo {
     fn test(&self) -> bool {
         self.test
     }
+
+    #[inline]
+    fn test_filter(&self) -> Option<&str> {
+        self.test_filter.as_deref()
+    }
+
+    #[inline]
+    fn expected_output(&self) -> Option<&str> {
+        self.expected_output.as_deref()
+    }
+
+    #[inline]
+    fn expected_exit_code(&self) -> i32 {
+        self.expected_exit_code
+    }
+
+    #[inline]
+    fn max_ns(&self) -> Option<u64> {
+        self.max_ns
+    }
+
+    #[inline]
+    fn style_check(&self) -> bool {
+        self.style_check
+    }
+
+    #[inline]
+    fn forbidden(&self) -> &[impl AsRef<str>] {
+        &self.forbidden
+    }
+
+    #[inline]
+    fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    #[inline]
+    fn multi_file(&self) -> bool {
+        self.multi_file
+    }
 }
 
 /// The deserialized `info.toml` file.
@@ -111,9 +208,211 @@ impl InfoFile {
             bail!("{NO_EXERCISES_ERR}");
         }
 
+        slf.check_requirements()?;
+
         Ok(slf)
     }
+
+    // Check that all required exercises exist and that the requirements don't have a cycle.
+    fn check_requirements(&self) -> Result<()> {
+        let name_to_ind = self
+            .exercises
+            .iter()
+            .enumerate()
+            .map(|(ind, exercise)| (exercise.name.as_str(), ind))
+            .collect::<HashMap<_, _>>();
+
+        let mut requirements = Vec::with_capacity(self.exercises.len());
+        for exercise in &self.exercises {
+            let exercise_requirements = exercise
+                .requires
+                .iter()
+                .map(|required| {
+                    name_to_ind.get(required.as_str()).copied().with_context(|| {
+                        format!(
+                            "The exercise `{}` requires the unknown exercise `{required}` in the `info.toml` file",
+                            exercise.name,
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            requirements.push(exercise_requirements);
+        }
+
+        if let Some(cycle) = find_cycle(&requirements) {
+            let cycle = cycle
+                .into_iter()
+                .map(|ind| self.exercises[ind].name.as_str())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            bail!("The requirements of exercises in the `info.toml` file contain a cycle: {cycle}");
+        }
+
+        Ok(())
+    }
+
+    /// Remove the exercises listed in the ignore file if it exists.
+    /// Only supported for third-party exercises because the official ones are embedded by index.
+    pub fn remove_ignored_exercises(&mut self) -> Result<()> {
+        let content = match fs::read_to_string(IGNORE_FILE_NAME) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(Error::from(e).context(format!("Failed to read {IGNORE_FILE_NAME}")));
+            }
+        };
+
+        for name in self.retain_not_ignored(&content) {
+            log::warn!("The exercise `{name}` in {IGNORE_FILE_NAME} doesn't exist");
+        }
+
+        if self.exercises.is_empty() {
+            bail!("All exercises are ignored in {IGNORE_FILE_NAME}");
+        }
+
+        Ok(())
+    }
+
+    // Empty lines and lines starting with `#` are skipped.
+    // Returns the ignored names without a matching exercise.
+    fn retain_not_ignored<'a>(&mut self, ignore_file_content: &'a str) -> Vec<&'a str> {
+        let ignored_names = ignore_file_content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect::<Vec<_>>();
+
+        let unknown_names = ignored_names
+            .iter()
+            .filter(|name| {
+                !self
+                    .exercises
+                    .iter()
+                    .any(|exercise| exercise.name == **name)
+            })
+            .copied()
+            .collect();
+        self.exercises
+            .retain(|exercise| !ignored_names.contains(&exercise.name.as_str()));
+
+        unknown_names
+    }
 }
 
 const NO_EXERCISES_ERR: &str = "There are no exercises yet!
 Add at least one exercise before testing.";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    New,
+    InProgress,
+    Finished,
+}
+
+// Depth-first search for a cycle in the graph of requirements given as indices.
+// Returns the indices on the cycle with the first one repeated at the end.
+fn find_cycle(requirements: &[Vec<usize>]) -> Option<Vec<usize>> {
+    fn visit(
+        ind: usize,
+        requirements: &[Vec<usize>],
+        visits: &mut [Visit],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        match visits[ind] {
+            Visit::Finished => return None,
+            Visit::InProgress => {
+                // The path contains the index because it is in progress.
+                let start = path.iter().position(|&path_ind| path_ind == ind)?;
+                let mut cycle = path[start..].to_vec();
+                cycle.push(ind);
+                return Some(cycle);
+            }
+            Visit::New => (),
+        }
+
+        visits[ind] = Visit::InProgress;
+        path.push(ind);
+        for &required in &requirements[ind] {
+            if let Some(cycle) = visit(required, requirements, visits, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        visits[ind] = Visit::Finished;
+
+        None
+    }
+
+    let mut visits = vec![Visit::New; requirements.len()];
+    let mut path = Vec::new();
+    (0..requirements.len()).find_map(|ind| visit(ind, requirements, &mut visits, &mut path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info_file(names: &[&str]) -> InfoFile {
+        InfoFile {
+            format_version: CURRENT_FORMAT_VERSION,
+            welcome_message: None,
+            final_message: None,
+            exercises: names
+                .iter()
+                .map(|name| ExerciseInfo {
+                    name: String::from(*name),
+                    dir: None,
+                    test: true,
+                    strict_clippy: false,
+                    test_filter: None,
+                    expected_output: None,
+                    expected_exit_code: 0,
+                    max_ns: None,
+                    style_check: false,
+                    tags: Vec::new(),
+                    forbidden: Vec::new(),
+                    multi_file: false,
+                    difficulty: None,
+                    estimated_minutes: None,
+                    requires: Vec::new(),
+                    env: BTreeMap::new(),
+                    edition: None,
+                    hint: String::new(),
+                    skip_check_unsolved: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn ignored_exercises() {
+        let mut info_file = info_file(&["a", "b", "c", "d"]);
+        let unknown_names = info_file.retain_not_ignored("# Not ready\nb\n\n  d  \ne\n#a\n");
+
+        assert_eq!(unknown_names, ["e"]);
+        let names = info_file
+            .exercises
+            .iter()
+            .map(|exercise| exercise.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a", "c"]);
+    }
+
+    #[test]
+    fn requirement_cycles() {
+        assert_eq!(find_cycle(&[vec![], vec![0], vec![0, 1]]), None);
+        assert_eq!(find_cycle(&[vec![0]]), Some(vec![0, 0]));
+        assert_eq!(
+            find_cycle(&[vec![], vec![2], vec![3], vec![1]]),
+            Some(vec![1, 2, 3, 1]),
+        );
+
+        let mut info_file = info_file(&["a", "b"]);
+        info_file.exercises[1].requires = vec![String::from("a")];
+        assert!(info_file.check_requirements().is_ok());
+        info_file.exercises[0].requires = vec![String::from("b")];
+        assert!(info_file.check_requirements().is_err());
+        info_file.exercises[0].requires = vec![String::from("c")];
+        assert!(info_file.check_requirements().is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::{Context, Result};
+use crossterm::{
+    QueueableCommand, cursor,
+    event::DisableMouseCapture,
+    terminal::{EnableLineWrap, LeaveAlternateScreen, disable_raw_mode},
+};
+use std::{
+    io::{self, Write},
+    panic,
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+};
+
+use crate::cmd::kill_running_children;
+
+/// Exit code of a process terminated by `SIGINT` (128 + 2).
+const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// The list is shown in the alternate screen with raw mode enabled.
+pub static LIST_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// The original terminal attributes while the watch mode has changed them.
+#[cfg(not(windows))]
+pub static WATCH_TERMIOS: std::sync::Mutex<Option<rustix::termios::Termios>> =
+    std::sync::Mutex::new(None);
+
+fn restore_terminal() {
+    // The main thread might hold the stdout lock which would lead to a deadlock.
+    // Therefore, write to stderr which is the same terminal in an interactive session.
+    let mut stderr = io::stderr();
+
+    if LIST_ACTIVE.load(Relaxed) {
+        let _ = stderr
+            .queue(LeaveAlternateScreen)
+            .and_then(|stderr| stderr.queue(cursor::Show))
+            .and_then(|stderr| stderr.queue(EnableLineWrap))
+            .and_then(|stderr| stderr.queue(DisableMouseCapture));
+        let _ = disable_raw_mode();
+    }
+
+    #[cfg(not(windows))]
+    if let Some(termios) = WATCH_TERMIOS
+        .lock()
+        .ok()
+        .and_then(|mut termios| termios.take())
+    {
+        let _ = rustix::termios::tcsetattr(
+            rustix::stdio::stdin(),
+            rustix::termios::OptionalActions::Now,
+            &termios,
+        );
+    }
+
+    let _ = stderr.write_all(b"\n");
+    let _ = stderr.flush();
+}
+
+/// Kill all running child processes and restore the terminal on Ctrl-C before exiting.
+/// Also handles `SIGTERM` and `SIGHUP` (e.g. when the terminal is closed).
+/// The progress doesn't need to be saved here because the state file is written after every change.
+pub fn set_handler() -> Result<()> {
+    ctrlc::set_handler(|| {
+        kill_running_children();
+        restore_terminal();
+
+        // The handler runs in its own thread. There is no way to return an exit code from `main`.
+        #[allow(clippy::disallowed_methods)]
+        std::process::exit(INTERRUPTED_EXIT_CODE);
+    })
+    .context("Failed to set the Ctrl-C handler")
+}
+
+/// Restore the terminal before printing the panic message.
+/// Unwinding is disabled (`panic = "abort"`), so the normal cleanup paths never run on panic.
+pub fn set_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        restore_terminal();
+        default_hook(panic_info);
+    }));
+}
@@ -0,0 +1,70 @@
+//! The core of Rustlings to embed it in other programs like graders.
+//!
+//! Run the command line program `rustlings` to solve the exercises.
+//!
+//! ```no_run
+//! use rustlings::{cmd::CmdRunner, exercise::RunnableExercise, info_file::InfoFile};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! // Disable colors in the returned output.
+//! rustlings::disable_styling();
+//!
+//! let info_file = InfoFile::parse()?;
+//! let cmd_runner = CmdRunner::build()?;
+//!
+//! for exercise in &info_file.exercises {
+//!     let result = exercise.check_exercise(&cmd_runner)?;
+//!     println!("{}: {}", exercise.name, result.success);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod app_state;
+pub mod cmd;
+pub mod exercise;
+pub mod info_file;
+
+mod cargo_toml;
+mod diff;
+mod embedded;
+mod history;
+mod list;
+
+// Modules of the command line program. They aren't part of the library API.
+#[doc(hidden)]
+pub mod badge;
+#[doc(hidden)]
+pub mod check_all;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod dev;
+#[doc(hidden)]
+pub mod export;
+#[doc(hidden)]
+pub mod graph;
+#[doc(hidden)]
+pub mod i18n;
+#[doc(hidden)]
+pub mod init;
+#[doc(hidden)]
+pub mod interrupt;
+#[doc(hidden)]
+pub mod lint;
+#[doc(hidden)]
+pub mod logger;
+#[doc(hidden)]
+pub mod run;
+#[doc(hidden)]
+pub mod search;
+#[doc(hidden)]
+pub mod state;
+#[doc(hidden)]
+pub mod stats;
+#[doc(hidden)]
+pub mod term;
+#[doc(hidden)]
+pub mod watch;
+
+pub use term::disable_styling;
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
+use serde::{Deserialize, de::IgnoredAny};
+use std::io::{self, StdoutLock, Write};
+
+use crate::{
+    app_state::AppState,
+    exercise::{Exercise, OUTPUT_CAPACITY},
+    term::{self, QueueStyle},
+};
+
+// Parses the parts of the JSON messages of Cargo which are needed.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    level: String,
+    // Lints and errors have a code, summaries don't.
+    code: Option<IgnoredAny>,
+    rendered: Option<String>,
+}
+
+enum LintResult {
+    Lints(usize),
+    CompilationFailed,
+}
+
+// Run Clippy on an exercise, print its diagnostics and count the lints.
+fn lint_exercise(
+    stdout: &mut StdoutLock,
+    app_state: &AppState,
+    exercise: &Exercise,
+    output: &mut Vec<u8>,
+) -> Result<LintResult> {
+    output.clear();
+    let message_format = if term::styling() {
+        "json-diagnostic-rendered-ansi"
+    } else {
+        "json"
+    };
+    // `--profile test` is required to also check code with `#[cfg(test)]`.
+    // The exit status is only used to detect a missing Clippy because compilation errors are found
+    // in the messages.
+    let mut clippy_cmd = app_state
+        .cmd_runner()
+        .cargo("clippy", exercise.name, Some(output));
+    clippy_cmd.args(["--profile", "test", "--message-format", message_format]);
+    if !clippy_cmd.run("cargo clippy …")? {
+        app_state
+            .cmd_runner()
+            .ensure_clippy_installed(Some(output))?;
+    }
+
+    let mut n_lints = 0;
+    let mut compilation_failed = false;
+    for line in output.split(|c| *c == b'\n') {
+        let Ok(CargoMessage {
+            reason,
+            message: Some(diagnostic),
+        }) = serde_json::from_slice(line)
+        else {
+            continue;
+        };
+        if reason != "compiler-message" {
+            continue;
+        }
+
+        if diagnostic.level == "error" {
+            compilation_failed = true;
+        } else if diagnostic.code.is_some() {
+            n_lints += 1;
+        } else {
+            // Summaries like "1 warning emitted".
+            continue;
+        }
+
+        if let Some(rendered) = diagnostic.rendered {
+            stdout.write_all(rendered.as_bytes())?;
+        }
+    }
+
+    if compilation_failed {
+        Ok(LintResult::CompilationFailed)
+    } else {
+        Ok(LintResult::Lints(n_lints))
+    }
+}
+
+fn write_summary(stdout: &mut StdoutLock, results: &[(&Exercise, LintResult)]) -> io::Result<()> {
+    let name_width = results
+        .iter()
+        .map(|(exercise, _)| exercise.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut total = 0;
+
+    stdout.queue_style(SetAttribute(Attribute::Underlined))?;
+    stdout.write_all(b"Lints per exercise")?;
+    stdout.queue_style(ResetColor)?;
+    stdout.write_all(b"\n")?;
+
+    for (exercise, result) in results {
+        write!(stdout, "{:name_width$}  ", exercise.name)?;
+        match result {
+            LintResult::Lints(0) => {
+                stdout.queue_style(SetForegroundColor(Color::Green))?;
+                stdout.write_all(b"0")?;
+            }
+            LintResult::Lints(n_lints) => {
+                total += n_lints;
+                stdout.queue_style(SetForegroundColor(Color::Yellow))?;
+                write!(stdout, "{n_lints}")?;
+            }
+            LintResult::CompilationFailed => {
+                stdout.queue_style(SetForegroundColor(Color::Red))?;
+                stdout.write_all(b"doesn't compile")?;
+            }
+        }
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b"\n")?;
+    }
+
+    writeln!(stdout, "\nTotal: {total}")
+}
+
+/// Run Clippy on all exercises or only on the one with the given name and print the lints.
+/// Unlike checking the exercises, this doesn't change whether an exercise is done.
+pub fn lint(app_state: &AppState, name: Option<&str>) -> Result<()> {
+    let exercises = match name {
+        Some(name) => {
+            let exercise_ind = app_state.exercise_ind_by_name(name)?;
+            &app_state.exercises()[exercise_ind..=exercise_ind]
+        }
+        None => app_state.exercises(),
+    };
+
+    let mut stdout = io::stdout().lock();
+    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let mut results = Vec::with_capacity(exercises.len());
+
+    for exercise in exercises {
+        if exercise.missing() {
+            continue;
+        }
+
+        stdout.queue_style(SetAttribute(Attribute::Bold))?;
+        stdout.write_all(b"Linting ")?;
+        stdout.queue_style(ResetColor)?;
+        exercise.terminal_file_link(&mut stdout)?;
+        stdout.write_all(b"\n")?;
+        stdout.flush()?;
+
+        let result = lint_exercise(&mut stdout, app_state, exercise, &mut output)?;
+        results.push((exercise, result));
+    }
+
+    stdout.write_all(b"\n")?;
+    write_summary(&mut stdout, &results)?;
+
+    Ok(())
+}
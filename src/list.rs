@@ -2,16 +2,24 @@ use anyhow::{Context, Result};
 use crossterm::{
     QueueableCommand, cursor,
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEventKind,
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MouseEventKind,
     },
     terminal::{
         DisableLineWrap, EnableLineWrap, EnterAlternateScreen, LeaveAlternateScreen,
         disable_raw_mode, enable_raw_mode,
     },
 };
-use std::io::{self, StdoutLock, Write};
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, StdoutLock, Write},
+    mem,
+    process::Command,
+    sync::atomic::Ordering::Relaxed,
+};
 
-use crate::app_state::AppState;
+use crate::{app_state::AppState, interrupt};
 
 use self::state::{Filter, ListState};
 
@@ -21,6 +29,9 @@ mod state;
 fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()> {
     let mut list_state = ListState::build(app_state, stdout)?;
     let mut is_searching = false;
+    // Vi-style count prefix like in `10j` and whether the first `g` of `gg` was entered.
+    let mut count: Option<usize> = None;
+    let mut pending_g = false;
 
     loop {
         match event::read().context("Failed to read terminal event")? {
@@ -32,6 +43,11 @@ fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()>
 
                 list_state.message.clear();
 
+                // Raw mode turns Ctrl-C into a key event instead of an interrupt signal.
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Ok(());
+                }
+
                 if is_searching {
                     match key.code {
                         KeyCode::Esc | KeyCode::Enter => {
@@ -53,12 +69,45 @@ fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()>
                     continue;
                 }
 
+                // Any other key cancels a started count or `g`.
+                let entered_count = count.take();
+                let entered_g = mem::take(&mut pending_g);
+
                 match key.code {
+                    KeyCode::Char(c @ '0'..='9') if c != '0' || entered_count.is_some() => {
+                        let new_count = entered_count
+                            .unwrap_or(0)
+                            .saturating_mul(10)
+                            .saturating_add(usize::from(c as u8 - b'0'));
+                        count = Some(new_count);
+                        write!(list_state.message, "{new_count}")?;
+                    }
                     KeyCode::Char('q') => return Ok(()),
-                    KeyCode::Down | KeyCode::Char('j') => list_state.select_next(),
-                    KeyCode::Up | KeyCode::Char('k') => list_state.select_previous(),
-                    KeyCode::Home | KeyCode::Char('g') => list_state.select_first(),
-                    KeyCode::End | KeyCode::Char('G') => list_state.select_last(),
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        list_state.select_next(entered_count.unwrap_or(1));
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        list_state.select_previous(entered_count.unwrap_or(1));
+                    }
+                    KeyCode::Home => list_state.select_first(),
+                    // `gg` and `G` with a count select the row with that number like in Vim.
+                    KeyCode::Char('g') if entered_g => {
+                        list_state.select_row_number(entered_count.unwrap_or(1));
+                    }
+                    KeyCode::Char('g') => {
+                        // Wait for the second `g`.
+                        pending_g = true;
+                        count = entered_count;
+                        if let Some(entered_count) = entered_count {
+                            write!(list_state.message, "{entered_count}")?;
+                        }
+                        list_state.message.push('g');
+                    }
+                    KeyCode::Char('G') => match entered_count {
+                        Some(number) => list_state.select_row_number(number),
+                        None => list_state.select_last(),
+                    },
+                    KeyCode::End => list_state.select_last(),
                     KeyCode::Char('d') => {
                         if list_state.filter() == Filter::Done {
                             list_state.set_filter(Filter::None);
@@ -81,7 +130,36 @@ fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()>
                             );
                         }
                     }
+                    KeyCode::Char('a') => {
+                        if list_state.filter() != Filter::None
+                            || list_state.tag_filter().is_some()
+                            || list_state.max_difficulty().is_some()
+                        {
+                            list_state.set_filter(Filter::None);
+                            list_state.clear_tag_filter();
+                            list_state.clear_max_difficulty();
+                            list_state.message.push_str("Disabled the filter");
+                        }
+                    }
+                    KeyCode::Char('t') => list_state.next_tag_filter()?,
+                    KeyCode::Char('D') => list_state.next_max_difficulty()?,
+                    KeyCode::Char('m') => list_state.toggle_metadata(),
+                    KeyCode::Char('o') => {
+                        let sort = list_state.sort().next();
+                        list_state.set_sort(sort);
+                        write!(list_state.message, "Sorted by {}", sort.as_str())?;
+                    }
+                    KeyCode::Tab => list_state.toggle_preview(),
+                    KeyCode::Char('P') => {
+                        list_state.toggle_full_paths();
+                        if list_state.full_paths() {
+                            list_state.message.push_str("Showing the full paths");
+                        } else {
+                            list_state.message.push_str("Showing the relative paths");
+                        }
+                    }
                     KeyCode::Char('r') => list_state.reset_selected()?,
+                    KeyCode::Char('e') => edit_selected(&mut list_state, stdout)?,
                     KeyCode::Char('c') => {
                         if list_state.selected_to_current_exercise()? {
                             return Ok(());
@@ -97,8 +175,8 @@ fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()>
                 }
             }
             Event::Mouse(event) => match event.kind {
-                MouseEventKind::ScrollDown => list_state.select_next(),
-                MouseEventKind::ScrollUp => list_state.select_previous(),
+                MouseEventKind::ScrollDown => list_state.select_next(1),
+                MouseEventKind::ScrollUp => list_state.select_previous(1),
                 _ => continue,
             },
             Event::Resize(width, height) => list_state.set_term_size(width, height),
@@ -110,25 +188,88 @@ fn handle_list(app_state: &mut AppState, stdout: &mut StdoutLock) -> Result<()>
     }
 }
 
-pub fn list(app_state: &mut AppState) -> Result<()> {
-    let mut stdout = io::stdout().lock();
+// Open the selected exercise in the editor from `$VISUAL` or `$EDITOR`.
+// The list terminal is left while the editor is running.
+fn edit_selected(list_state: &mut ListState, stdout: &mut StdoutLock) -> Result<()> {
+    let Some(exercise_path) = list_state.selected_exercise_path()? else {
+        list_state.message.push_str("Nothing selected to edit!");
+        return Ok(());
+    };
+
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty());
+    let Some(editor) = editor else {
+        list_state.message.push_str(
+            "Set the environment variable VISUAL or EDITOR to edit exercises from the list",
+        );
+        return Ok(());
+    };
+
+    // The editor can contain arguments like `code --wait`.
+    let mut editor_args = editor.split_whitespace();
+    let Some(program) = editor_args.next() else {
+        return Ok(());
+    };
+
+    leave_list_terminal(stdout).context("Failed to restore the terminal for the editor")?;
+    let status = Command::new(program)
+        .args(editor_args)
+        .arg(exercise_path)
+        .status();
+    enter_list_terminal(stdout).context("Failed to set up the terminal for the list")?;
+
+    match status {
+        Ok(status) if status.success() => (),
+        Ok(status) => write!(
+            list_state.message,
+            "The editor `{editor}` exited with {status}"
+        )?,
+        Err(e) => write!(
+            list_state.message,
+            "Failed to run the editor `{editor}`: {e}"
+        )?,
+    }
+
+    Ok(())
+}
+
+fn enter_list_terminal(stdout: &mut StdoutLock) -> io::Result<()> {
     stdout
         .queue(EnterAlternateScreen)?
         .queue(cursor::Hide)?
         .queue(DisableLineWrap)?
         .queue(EnableMouseCapture)?;
-    enable_raw_mode()?;
+    enable_raw_mode()
+}
+
+// Disable raw mode even if writing to the terminal fails.
+fn leave_list_terminal(stdout: &mut StdoutLock) -> io::Result<()> {
+    let write_res = stdout
+        .queue(LeaveAlternateScreen)
+        .and_then(|stdout| stdout.queue(cursor::Show))
+        .and_then(|stdout| stdout.queue(EnableLineWrap))
+        .and_then(|stdout| stdout.queue(DisableMouseCapture))
+        .and_then(|stdout| stdout.flush());
+    let raw_mode_res = disable_raw_mode();
 
-    let res = handle_list(app_state, &mut stdout);
+    write_res.and(raw_mode_res)
+}
+
+pub fn list(app_state: &mut AppState) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    // Set before entering to also restore a partially set up terminal on Ctrl-C or panic.
+    interrupt::LIST_ACTIVE.store(true, Relaxed);
+
+    let res = enter_list_terminal(&mut stdout)
+        .context("Failed to set up the terminal for the list")
+        .and_then(|()| handle_list(app_state, &mut stdout));
 
     // Restore the terminal even if we got an error.
-    stdout
-        .queue(LeaveAlternateScreen)?
-        .queue(cursor::Show)?
-        .queue(EnableLineWrap)?
-        .queue(DisableMouseCapture)?
-        .flush()?;
-    disable_raw_mode()?;
-
-    res
+    let leave_res = leave_list_terminal(&mut stdout);
+    interrupt::LIST_ACTIVE.store(false, Relaxed);
+
+    res?;
+    leave_res.context("Failed to restore the terminal after the list")
 }
@@ -51,15 +51,22 @@ impl ScrollState {
         self.update_offset();
     }
 
-    pub fn select_next(&mut self) {
+    pub fn select_next(&mut self, n: usize) {
         if let Some(selected) = self.selected {
-            self.set_selected((selected + 1).min(self.n_rows - 1));
+            self.set_selected(selected.saturating_add(n).min(self.n_rows - 1));
         }
     }
 
-    pub fn select_previous(&mut self) {
+    pub fn select_previous(&mut self, n: usize) {
         if let Some(selected) = self.selected {
-            self.set_selected(selected.saturating_sub(1));
+            self.set_selected(selected.saturating_sub(n));
+        }
+    }
+
+    /// Select the row with the given index or the last row if the index is too high.
+    pub fn select_clamped(&mut self, row: usize) {
+        if self.n_rows > 0 {
+            self.set_selected(row.min(self.n_rows - 1));
         }
     }
 
@@ -102,3 +109,34 @@ impl ScrollState {
         self.update_offset();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_with_counts() {
+        let mut scroll_state = ScrollState::new(10, Some(0), 5);
+        scroll_state.set_max_n_rows_to_display(4);
+
+        scroll_state.select_next(3);
+        assert_eq!(scroll_state.selected(), Some(3));
+        scroll_state.select_next(100);
+        assert_eq!(scroll_state.selected(), Some(9));
+        assert_eq!(scroll_state.offset(), 6);
+        scroll_state.select_previous(4);
+        assert_eq!(scroll_state.selected(), Some(5));
+        scroll_state.select_previous(usize::MAX);
+        assert_eq!(scroll_state.selected(), Some(0));
+
+        scroll_state.select_clamped(7);
+        assert_eq!(scroll_state.selected(), Some(7));
+        scroll_state.select_clamped(25);
+        assert_eq!(scroll_state.selected(), Some(9));
+
+        let mut empty = ScrollState::new(0, None, 5);
+        empty.select_next(2);
+        empty.select_clamped(3);
+        assert_eq!(empty.selected(), None);
+    }
+}
@@ -8,23 +8,37 @@ use crossterm::{
     terminal::{self, BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate},
 };
 use std::{
+    env,
     fmt::Write as _,
     io::{self, StdoutLock, Write},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app_state::AppState,
-    exercise::Exercise,
-    term::{CountedWrite, MaxLenWriter, progress_bar},
+    exercise::{Exercise, read_source_lossy},
+    i18n,
+    term::{CountedWrite, MaxLenWriter, QueueStyle, progress_bar, terminal_file_link},
 };
 
 use super::scroll_state::ScrollState;
 
 const COL_SPACING: usize = 2;
+const MAX_DIFFICULTY: u8 = 5;
+// The width of the difficulty column which is the width of its title.
+const DIFFICULTY_COL_WIDTH: usize = 10;
+// The maximum number of lines of the exercise file shown in the preview.
+const PREVIEW_SOURCE_LINES: usize = 15;
 const SELECTED_ROW_ATTRIBUTES: Attributes = Attributes::none()
     .with(Attribute::Reverse)
     .with(Attribute::Bold);
 
+// The display width of the widest string, but at least `min_width`.
+// Characters like `ü` take one column although they take multiple bytes. Wide ones take two.
+fn max_width<'s>(min_width: usize, strs: impl Iterator<Item = &'s str>) -> usize {
+    strs.map(UnicodeWidthStr::width).fold(min_width, usize::max)
+}
+
 fn next_ln(stdout: &mut StdoutLock) -> io::Result<()> {
     stdout
         .queue(Clear(ClearType::UntilNewLine))?
@@ -39,6 +53,45 @@ pub enum Filter {
     None,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Sort {
+    /// The order in the `info.toml` file.
+    Default,
+    Name,
+    /// Pending exercises first.
+    State,
+    /// Exercises with tests first, then the ones with strict Clippy lints.
+    Mode,
+    /// Easiest exercises first. Exercises without a difficulty last.
+    Difficulty,
+    /// Shortest estimated time first. Exercises without an estimated time last.
+    Time,
+}
+
+impl Sort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Name,
+            Self::Name => Self::State,
+            Self::State => Self::Mode,
+            Self::Mode => Self::Difficulty,
+            Self::Difficulty => Self::Time,
+            Self::Time => Self::Default,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Default => "DEFAULT",
+            Self::Name => "NAME",
+            Self::State => "STATE",
+            Self::Mode => "MODE",
+            Self::Difficulty => "DIFFICULTY",
+            Self::Time => "TIME",
+        }
+    }
+}
+
 pub struct ListState<'a> {
     /// Footer message to be displayed if not empty.
     pub message: String,
@@ -47,10 +100,32 @@ pub struct ListState<'a> {
     scroll_state: ScrollState,
     name_col_padding: Vec<u8>,
     path_col_padding: Vec<u8>,
+    // Show the absolute paths instead of the ones relative to the Rustlings directory.
+    full_paths: bool,
     filter: Filter,
+    // All tags of the exercises sorted and without duplicates.
+    tags: Vec<&'static str>,
+    // Only show the exercises with this tag.
+    tag_filter: Option<&'static str>,
+    // Only show the exercises with a difficulty up to this one.
+    max_difficulty: Option<u8>,
+    // Some exercises have a difficulty.
+    has_difficulty: bool,
+    // Some exercises have a difficulty or an estimated time.
+    has_metadata: bool,
+    // Show the columns of the difficulty and the estimated time.
+    show_metadata: bool,
+    sort: Sort,
+    // Exercise indices in the displayed order.
+    order: Vec<usize>,
     term_width: u16,
     term_height: u16,
     show_footer: bool,
+    show_preview: bool,
+    // 0 if the preview isn't shown.
+    preview_height: u16,
+    // Show letters instead of emojis for the exercise modes.
+    no_emoji: bool,
 }
 
 impl<'a> ListState<'a> {
@@ -58,20 +133,29 @@ impl<'a> ListState<'a> {
         stdout.queue(Clear(ClearType::All))?;
 
         let name_col_title_len = 4;
-        let path_col_title_len = 4;
-        let (name_col_width, path_col_width) = app_state.exercises().iter().fold(
-            (name_col_title_len, path_col_title_len),
-            |(name_col_width, path_col_width), exercise| {
-                (
-                    name_col_width.max(exercise.name.len()),
-                    path_col_width.max(exercise.path.len()),
-                )
-            },
+        let name_col_width = max_width(
+            name_col_title_len,
+            app_state.exercises().iter().map(|exercise| exercise.name),
         );
         let name_col_padding = vec![b' '; name_col_width + COL_SPACING];
-        let path_col_padding = vec![b' '; path_col_width];
 
         let filter = Filter::None;
+        let mut tags = app_state
+            .exercises()
+            .iter()
+            .flat_map(|exercise| exercise.tags.iter().copied())
+            .collect::<Vec<_>>();
+        tags.sort_unstable();
+        tags.dedup();
+        let has_difficulty = app_state
+            .exercises()
+            .iter()
+            .any(|exercise| exercise.difficulty.is_some());
+        let has_metadata = has_difficulty
+            || app_state
+                .exercises()
+                .iter()
+                .any(|exercise| exercise.estimated_minutes.is_some());
         let n_rows_with_filter = app_state.exercises().len();
         let selected = app_state.current_exercise_ind();
 
@@ -84,14 +168,28 @@ impl<'a> ListState<'a> {
             app_state,
             scroll_state,
             name_col_padding,
-            path_col_padding,
+            // Set by `update_path_col_padding`
+            path_col_padding: Vec::new(),
+            full_paths: false,
             filter,
+            tags,
+            tag_filter: None,
+            max_difficulty: None,
+            has_difficulty,
+            has_metadata,
+            show_metadata: has_metadata,
+            sort: Sort::Default,
+            order: (0..n_rows_with_filter).collect(),
             // Set by `set_term_size`
             term_width: 0,
             term_height: 0,
             show_footer: true,
+            show_preview: false,
+            preview_height: 0,
+            no_emoji: env::var_os("NO_EMOJI").is_some_and(|value| !value.is_empty()),
         };
 
+        slf.update_path_col_padding();
         slf.set_term_size(width, height);
         slf.draw(stdout)?;
 
@@ -111,10 +209,73 @@ impl<'a> ListState<'a> {
         let footer_height = 3;
         self.show_footer = height > header_height + footer_height;
 
-        self.scroll_state.set_max_n_rows_to_display(
-            height.saturating_sub(header_height + u16::from(self.show_footer) * footer_height)
-                as usize,
+        let body_height =
+            height.saturating_sub(header_height + u16::from(self.show_footer) * footer_height);
+        // The preview takes the lower half of the space below the header.
+        self.preview_height = if self.show_preview {
+            body_height / 2
+        } else {
+            0
+        };
+
+        self.scroll_state
+            .set_max_n_rows_to_display((body_height - self.preview_height) as usize);
+    }
+
+    /// Show or hide the preview of the selected exercise's hint and file.
+    pub fn toggle_preview(&mut self) {
+        self.show_preview = !self.show_preview;
+        self.set_term_size(self.term_width, self.term_height);
+    }
+
+    fn update_path_col_padding(&mut self) {
+        let path_col_title_len = 4;
+        let path_col_width = max_width(
+            path_col_title_len,
+            self.app_state
+                .exercises()
+                .iter()
+                .map(|exercise| self.displayed_path(exercise)),
         );
+        self.path_col_padding = vec![b' '; path_col_width];
+    }
+
+    /// Toggle between the paths relative to the Rustlings directory and the absolute paths.
+    pub fn toggle_full_paths(&mut self) {
+        self.full_paths = !self.full_paths;
+        self.update_path_col_padding();
+    }
+
+    #[inline]
+    pub fn full_paths(&self) -> bool {
+        self.full_paths
+    }
+
+    /// Show or hide the columns of the difficulty and the estimated time.
+    pub fn toggle_metadata(&mut self) {
+        if !self.has_metadata {
+            self.message
+                .push_str("No exercise has a difficulty or an estimated time");
+            return;
+        }
+
+        self.show_metadata = !self.show_metadata;
+        if self.show_metadata {
+            self.message
+                .push_str("Showing the difficulty and the estimated time");
+        } else {
+            self.message
+                .push_str("Hiding the difficulty and the estimated time");
+        }
+    }
+
+    fn displayed_path<'e>(&self, exercise: &'e Exercise) -> &'e str {
+        if self.full_paths {
+            // Fall back to the relative path if the file doesn't exist.
+            exercise.canonical_path.as_deref().unwrap_or(exercise.path)
+        } else {
+            exercise.path
+        }
     }
 
     fn draw_exercise_name(&self, writer: &mut MaxLenWriter, exercise: &Exercise) -> io::Result<()> {
@@ -129,9 +290,13 @@ impl<'a> ListState<'a> {
                 })
             {
                 writer.write_str(pre_highlight)?;
-                writer.stdout.queue(SetForegroundColor(Color::Magenta))?;
+                writer
+                    .stdout
+                    .queue_style(SetForegroundColor(Color::Magenta))?;
                 writer.write_str(highlight)?;
-                writer.stdout.queue(SetForegroundColor(Color::Reset))?;
+                writer
+                    .stdout
+                    .queue_style(SetForegroundColor(Color::Reset))?;
                 return writer.write_str(post_highlight);
             }
         }
@@ -139,16 +304,76 @@ impl<'a> ListState<'a> {
         writer.write_str(exercise.name)
     }
 
-    fn draw_rows(
-        &self,
-        stdout: &mut StdoutLock,
-        filtered_exercises: impl Iterator<Item = (usize, &'a Exercise)>,
-    ) -> io::Result<usize> {
+    // The sorted exercises with their indices which pass the filter.
+    fn displayed_exercises(&self) -> impl Iterator<Item = (usize, &Exercise)> {
+        let exercises = self.app_state.exercises();
+        let filter = self.filter;
+        let tag_filter = self.tag_filter;
+        let max_difficulty = self.max_difficulty;
+
+        self.order
+            .iter()
+            .map(move |&exercise_ind| (exercise_ind, &exercises[exercise_ind]))
+            .filter(move |(_, exercise)| match filter {
+                Filter::Done => exercise.done,
+                Filter::Pending => !exercise.done,
+                Filter::None => true,
+            })
+            .filter(move |(_, exercise)| tag_filter.is_none_or(|tag| exercise.has_tag(tag)))
+            .filter(move |(_, exercise)| {
+                max_difficulty.is_none_or(|max_difficulty| {
+                    exercise
+                        .difficulty
+                        .is_some_and(|difficulty| difficulty <= max_difficulty)
+                })
+            })
+    }
+
+    // The difficulty like `3/5` and the estimated time like `15 min`. `-` if not set.
+    fn draw_metadata(writer: &mut MaxLenWriter, exercise: &Exercise) -> io::Result<()> {
+        let difficulty = exercise.difficulty.map_or_else(
+            || String::from("-"),
+            |difficulty| format!("{difficulty}/{MAX_DIFFICULTY}"),
+        );
+        writer.write_str(&format!("{difficulty:DIFFICULTY_COL_WIDTH$}"))?;
+        writer.write_ascii(&[b' '; COL_SPACING])?;
+
+        match exercise.estimated_minutes {
+            Some(minutes) => writer.write_str(&format!("{minutes} min")),
+            None => writer.write_ascii(b"-"),
+        }
+    }
+
+    // A glyph with the width of two ASCII chars showing what is checked.
+    fn draw_mode(&self, writer: &mut MaxLenWriter, exercise: &Exercise) -> io::Result<()> {
+        if self.no_emoji {
+            return writer.write_ascii(if exercise.test {
+                b"T "
+            } else if exercise.strict_clippy {
+                b"L "
+            } else {
+                b"C "
+            });
+        }
+
+        if exercise.test {
+            writer.add_to_len(2);
+            writer.stdout.write_all("🧪".as_bytes())
+        } else if exercise.strict_clippy {
+            writer.add_to_len(2);
+            writer.stdout.write_all("📎".as_bytes())
+        } else {
+            writer.write_str("▶ ")
+        }
+    }
+
+    fn draw_rows(&self, stdout: &mut StdoutLock) -> io::Result<usize> {
         let current_exercise_ind = self.app_state.current_exercise_ind();
         let row_offset = self.scroll_state.offset();
         let mut n_displayed_rows = 0;
 
-        for (exercise_ind, exercise) in filtered_exercises
+        for (exercise_ind, exercise) in self
+            .displayed_exercises()
             .skip(row_offset)
             .take(self.scroll_state.max_n_rows_to_display())
         {
@@ -158,6 +383,7 @@ impl<'a> ListState<'a> {
                 // The crab emoji has the width of two ascii chars.
                 writer.add_to_len(2);
                 writer.stdout.write_all("🦀".as_bytes())?;
+                // Not skipped without styling because the selection must stay visible.
                 writer
                     .stdout
                     .queue(SetAttributes(SELECTED_ROW_ATTRIBUTES))?;
@@ -166,37 +392,60 @@ impl<'a> ListState<'a> {
             }
 
             if exercise_ind == current_exercise_ind {
-                writer.stdout.queue(SetForegroundColor(Color::Red))?;
+                writer.stdout.queue_style(SetForegroundColor(Color::Red))?;
                 writer.write_ascii(b">>>>>>>  ")?;
             } else {
                 writer.write_ascii(b"         ")?;
             }
 
-            if exercise.done {
-                writer.stdout.queue(SetForegroundColor(Color::Green))?;
+            if exercise.missing() {
+                writer.stdout.queue_style(SetForegroundColor(Color::Red))?;
+                writer.write_ascii(b"MISSING")?;
+            } else if exercise.done {
+                writer
+                    .stdout
+                    .queue_style(SetForegroundColor(Color::Green))?;
                 writer.write_ascii(b"DONE   ")?;
+            } else if exercise.skipped {
+                writer.stdout.queue_style(SetForegroundColor(Color::Cyan))?;
+                writer.write_ascii(b"SKIPPED")?;
             } else {
-                writer.stdout.queue(SetForegroundColor(Color::Yellow))?;
+                writer
+                    .stdout
+                    .queue_style(SetForegroundColor(Color::Yellow))?;
                 writer.write_ascii(b"PENDING")?;
             }
-            writer.stdout.queue(SetForegroundColor(Color::Reset))?;
+            writer
+                .stdout
+                .queue_style(SetForegroundColor(Color::Reset))?;
             writer.write_ascii(b"  ")?;
 
+            self.draw_mode(&mut writer, exercise)?;
+            writer.write_ascii(b" ")?;
+
             self.draw_exercise_name(&mut writer, exercise)?;
 
-            writer.write_ascii(&self.name_col_padding[exercise.name.len()..])?;
+            writer.write_ascii(&self.name_col_padding[exercise.name.width()..])?;
 
+            let path = self.displayed_path(exercise);
             // The list links aren't shown correctly in VS Code on Windows.
             // But VS Code shows its own links anyway.
-            if self.app_state.vs_code() {
-                writer.write_str(exercise.path)?;
-            } else {
-                exercise.terminal_file_link(&mut writer)?;
+            match exercise.canonical_path.as_deref() {
+                Some(canonical_path) if !self.app_state.vs_code() => {
+                    terminal_file_link(&mut writer, path, canonical_path, Color::Blue)?;
+                }
+                _ => writer.write_str(path)?,
             }
 
-            writer.write_ascii(&self.path_col_padding[exercise.path.len()..])?;
+            writer.write_ascii(&self.path_col_padding[path.width()..])?;
+
+            if self.show_metadata {
+                writer.write_ascii(&[b' '; COL_SPACING])?;
+                Self::draw_metadata(&mut writer, exercise)?;
+            }
 
             next_ln(stdout)?;
+            // Not skipped without styling to reset the attributes of the selected row.
             stdout.queue(ResetColor)?;
             n_displayed_rows += 1;
         }
@@ -204,6 +453,53 @@ impl<'a> ListState<'a> {
         Ok(n_displayed_rows)
     }
 
+    fn draw_preview(&self, stdout: &mut StdoutLock) -> io::Result<()> {
+        let selected_exercise = self
+            .scroll_state
+            .selected()
+            .and_then(|selected| self.displayed_exercises().nth(selected))
+            .map(|(_, exercise)| exercise);
+
+        let mut writer = MaxLenWriter::new(stdout, self.term_width as usize);
+        writer.write_str("── ")?;
+        let Some(exercise) = selected_exercise else {
+            writer.write_ascii(b"Nothing selected")?;
+            next_ln(stdout)?;
+            for _ in 1..self.preview_height {
+                next_ln(stdout)?;
+            }
+            return Ok(());
+        };
+        writer.stdout.queue_style(SetAttribute(Attribute::Bold))?;
+        writer.write_str(exercise.name)?;
+        writer.stdout.queue_style(ResetColor)?;
+        next_ln(stdout)?;
+
+        // A missing file is already marked in the list.
+        let source = read_source_lossy(exercise.path).unwrap_or_default();
+
+        let hint_lines = exercise.hint.lines().map(|line| (line, Color::Cyan));
+        let source_lines = source
+            .lines()
+            .take(PREVIEW_SOURCE_LINES)
+            .map(|line| (line, Color::Reset));
+        let mut lines = hint_lines
+            .chain(Some(("", Color::Reset)))
+            .chain(source_lines);
+
+        for _ in 1..self.preview_height {
+            if let Some((line, color)) = lines.next() {
+                let mut writer = MaxLenWriter::new(stdout, self.term_width as usize);
+                writer.stdout.queue_style(SetForegroundColor(color))?;
+                writer.write_str(line)?;
+                writer.stdout.queue_style(ResetColor)?;
+            }
+            next_ln(stdout)?;
+        }
+
+        Ok(())
+    }
+
     pub fn draw(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
         if self.term_height == 0 {
             return Ok(());
@@ -213,25 +509,29 @@ impl<'a> ListState<'a> {
 
         // Header
         let mut writer = MaxLenWriter::new(stdout, self.term_width as usize);
-        writer.write_ascii(b"  Current  State    Name")?;
+        writer.write_ascii(b"  Current  State       Name")?;
         writer.write_ascii(&self.name_col_padding[4..])?;
         writer.write_ascii(b"Path")?;
+        if self.show_metadata {
+            writer.write_ascii(&self.path_col_padding[4..])?;
+            writer.write_ascii(&[b' '; COL_SPACING])?;
+            writer.write_ascii(b"Difficulty")?;
+            writer.write_ascii(&[b' '; COL_SPACING])?;
+            writer.write_ascii(b"Time")?;
+        }
         next_ln(stdout)?;
 
         // Rows
-        let iter = self.app_state.exercises().iter().enumerate();
-        let n_displayed_rows = match self.filter {
-            Filter::Done => self.draw_rows(stdout, iter.filter(|(_, exercise)| exercise.done))?,
-            Filter::Pending => {
-                self.draw_rows(stdout, iter.filter(|(_, exercise)| !exercise.done))?
-            }
-            Filter::None => self.draw_rows(stdout, iter)?,
-        };
+        let n_displayed_rows = self.draw_rows(stdout)?;
 
         for _ in 0..self.scroll_state.max_n_rows_to_display() - n_displayed_rows {
             next_ln(stdout)?;
         }
 
+        if self.preview_height > 0 {
+            self.draw_preview(stdout)?;
+        }
+
         if self.show_footer {
             progress_bar(
                 &mut MaxLenWriter::new(stdout, self.term_width as usize),
@@ -245,43 +545,62 @@ impl<'a> ListState<'a> {
             if self.message.is_empty() {
                 // Help footer message
                 if self.scroll_state.selected().is_some() {
-                    writer.write_str("↓/j ↑/k home/g end/G | <c>ontinue at | <r>eset exercise")?;
+                    writer.write_str(i18n::tr("list_footer_navigation"))?;
                     next_ln(stdout)?;
                     writer = MaxLenWriter::new(stdout, self.term_width as usize);
 
-                    writer.write_ascii(b"<s>earch | filter ")?;
+                    writer.write_str(i18n::tr("list_footer_search"))?;
+                    writer.write_str(i18n::tr("list_footer_sort"))?;
+                    writer.write_str(i18n::tr("list_footer_preview"))?;
+                    if !self.tags.is_empty() {
+                        writer.write_str(i18n::tr("list_footer_tag"))?;
+                    }
+                    if self.has_difficulty {
+                        writer.write_str(i18n::tr("list_footer_difficulty"))?;
+                    }
+                    if self.has_metadata {
+                        writer.write_str(i18n::tr("list_footer_metadata"))?;
+                    }
+                    writer.write_str(i18n::tr("list_footer_paths"))?;
+                    writer.write_str(i18n::tr("list_footer_filter"))?;
                 } else {
                     // Nothing selected (and nothing shown), so only display filter and quit.
-                    writer.write_ascii(b"filter ")?;
+                    writer.write_str(i18n::tr("list_footer_filter"))?;
                 }
 
-                match self.filter {
-                    Filter::Done => {
-                        writer
-                            .stdout
-                            .queue(SetForegroundColor(Color::Magenta))?
-                            .queue(SetAttribute(Attribute::Underlined))?;
-                        writer.write_ascii(b"<d>one")?;
-                        writer.stdout.queue(ResetColor)?;
-                        writer.write_ascii(b"/<p>ending")?;
+                for (ind, (filter, key)) in [
+                    (Filter::Done, "list_footer_done"),
+                    (Filter::Pending, "list_footer_pending"),
+                    (Filter::None, "list_footer_all"),
+                ]
+                .into_iter()
+                .enumerate()
+                {
+                    if ind > 0 {
+                        writer.write_ascii(b"/")?;
                     }
-                    Filter::Pending => {
-                        writer.write_ascii(b"<d>one/")?;
+
+                    // Highlight the active filter.
+                    if filter != Filter::None && filter == self.filter {
                         writer
                             .stdout
-                            .queue(SetForegroundColor(Color::Magenta))?
-                            .queue(SetAttribute(Attribute::Underlined))?;
-                        writer.write_ascii(b"<p>ending")?;
-                        writer.stdout.queue(ResetColor)?;
+                            .queue_style(SetForegroundColor(Color::Magenta))?
+                            .queue_style(SetAttribute(Attribute::Underlined))?;
+                        writer.write_str(i18n::tr(key))?;
+                        writer.stdout.queue_style(ResetColor)?;
+                    } else {
+                        writer.write_str(i18n::tr(key))?;
                     }
-                    Filter::None => writer.write_ascii(b"<d>one/<p>ending")?,
                 }
 
-                writer.write_ascii(b" | <q>uit list")?;
+                writer.write_ascii(b" | ")?;
+                writer.write_str(i18n::tr("list_footer_quit"))?;
             } else {
-                writer.stdout.queue(SetForegroundColor(Color::Magenta))?;
+                writer
+                    .stdout
+                    .queue_style(SetForegroundColor(Color::Magenta))?;
                 writer.write_str(&self.message)?;
-                stdout.queue(ResetColor)?;
+                stdout.queue_style(ResetColor)?;
                 next_ln(stdout)?;
             }
 
@@ -292,25 +611,62 @@ impl<'a> ListState<'a> {
     }
 
     fn update_rows(&mut self) {
-        let n_rows = match self.filter {
-            Filter::Done => self
-                .app_state
-                .exercises()
-                .iter()
-                .filter(|exercise| exercise.done)
-                .count(),
-            Filter::Pending => self
-                .app_state
-                .exercises()
-                .iter()
-                .filter(|exercise| !exercise.done)
-                .count(),
-            Filter::None => self.app_state.exercises().len(),
-        };
-
+        let n_rows = self.displayed_exercises().count();
         self.scroll_state.set_n_rows(n_rows);
     }
 
+    fn update_order(&mut self) {
+        let exercises = self.app_state.exercises();
+        self.order.clear();
+        self.order.extend(0..exercises.len());
+
+        // The sorting is stable. Exercises with the same key stay in the default order.
+        match self.sort {
+            Sort::Default => (),
+            Sort::Name => self
+                .order
+                .sort_by_key(|&exercise_ind| exercises[exercise_ind].name),
+            Sort::State => self
+                .order
+                .sort_by_key(|&exercise_ind| exercises[exercise_ind].done),
+            Sort::Mode => self.order.sort_by_key(|&exercise_ind| {
+                let exercise = &exercises[exercise_ind];
+                (!exercise.test, !exercise.strict_clippy)
+            }),
+            Sort::Difficulty => self.order.sort_by_key(|&exercise_ind| {
+                let difficulty = exercises[exercise_ind].difficulty;
+                (difficulty.is_none(), difficulty)
+            }),
+            Sort::Time => self.order.sort_by_key(|&exercise_ind| {
+                let estimated_minutes = exercises[exercise_ind].estimated_minutes;
+                (estimated_minutes.is_none(), estimated_minutes)
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn sort(&self) -> Sort {
+        self.sort
+    }
+
+    /// Change the sorting while keeping the selected exercise selected.
+    pub fn set_sort(&mut self, sort: Sort) {
+        let selected_exercise_ind = self
+            .scroll_state
+            .selected()
+            .and_then(|selected| self.selected_to_exercise_ind(selected).ok());
+
+        self.sort = sort;
+        self.update_order();
+
+        if let Some(selected) = selected_exercise_ind.and_then(|selected_exercise_ind| {
+            self.displayed_exercises()
+                .position(|(exercise_ind, _)| exercise_ind == selected_exercise_ind)
+        }) {
+            self.scroll_state.set_selected(selected);
+        }
+    }
+
     #[inline]
     pub fn filter(&self) -> Filter {
         self.filter
@@ -322,13 +678,91 @@ impl<'a> ListState<'a> {
     }
 
     #[inline]
-    pub fn select_next(&mut self) {
-        self.scroll_state.select_next();
+    pub fn tag_filter(&self) -> Option<&'static str> {
+        self.tag_filter
+    }
+
+    pub fn clear_tag_filter(&mut self) {
+        self.tag_filter = None;
+        self.update_rows();
+    }
+
+    /// Filter by the next tag in alphabetical order.
+    /// Disables the tag filter after the last tag.
+    pub fn next_tag_filter(&mut self) -> Result<()> {
+        let next_ind = match self.tag_filter {
+            Some(tag) => self
+                .tags
+                .iter()
+                .position(|&t| t == tag)
+                .map_or(0, |ind| ind + 1),
+            None => 0,
+        };
+        self.tag_filter = self.tags.get(next_ind).copied();
+        self.update_rows();
+
+        match self.tag_filter {
+            Some(tag) => write!(
+                self.message,
+                "Enabled the filter by the tag `{tag}` │ Press t for the next tag",
+            )?,
+            None if self.tags.is_empty() => self.message.push_str("No exercise has tags"),
+            None => self.message.push_str("Disabled the tag filter"),
+        }
+
+        Ok(())
     }
 
     #[inline]
-    pub fn select_previous(&mut self) {
-        self.scroll_state.select_previous();
+    pub fn max_difficulty(&self) -> Option<u8> {
+        self.max_difficulty
+    }
+
+    pub fn clear_max_difficulty(&mut self) {
+        self.max_difficulty = None;
+        self.update_rows();
+    }
+
+    /// Only show the exercises up to the next higher difficulty.
+    /// Disables the difficulty filter after the highest difficulty.
+    pub fn next_max_difficulty(&mut self) -> Result<()> {
+        if !self.has_difficulty {
+            self.message.push_str("No exercise has a difficulty");
+            return Ok(());
+        }
+
+        self.max_difficulty = match self.max_difficulty {
+            Some(max_difficulty) if max_difficulty < MAX_DIFFICULTY => Some(max_difficulty + 1),
+            Some(_) => None,
+            None => Some(1),
+        };
+        self.update_rows();
+
+        match self.max_difficulty {
+            Some(max_difficulty) => write!(
+                self.message,
+                "Showing the exercises with a difficulty up to {max_difficulty}/{MAX_DIFFICULTY} │ Press D for the next difficulty",
+            )?,
+            None => self.message.push_str("Disabled the difficulty filter"),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn select_next(&mut self, n: usize) {
+        self.scroll_state.select_next(n);
+    }
+
+    #[inline]
+    pub fn select_previous(&mut self, n: usize) {
+        self.scroll_state.select_previous(n);
+    }
+
+    /// Select the row with the given number starting at 1 (clamped to the valid rows).
+    #[inline]
+    pub fn select_row_number(&mut self, number: usize) {
+        self.scroll_state.select_clamped(number.saturating_sub(1));
     }
 
     #[inline]
@@ -342,27 +776,10 @@ impl<'a> ListState<'a> {
     }
 
     fn selected_to_exercise_ind(&self, selected: usize) -> Result<usize> {
-        match self.filter {
-            Filter::Done => self
-                .app_state
-                .exercises()
-                .iter()
-                .enumerate()
-                .filter(|(_, exercise)| exercise.done)
-                .nth(selected)
-                .context("Invalid selection index")
-                .map(|(ind, _)| ind),
-            Filter::Pending => self
-                .app_state
-                .exercises()
-                .iter()
-                .enumerate()
-                .filter(|(_, exercise)| !exercise.done)
-                .nth(selected)
-                .context("Invalid selection index")
-                .map(|(ind, _)| ind),
-            Filter::None => Ok(selected),
-        }
+        self.displayed_exercises()
+            .nth(selected)
+            .context("Invalid selection index")
+            .map(|(ind, _)| ind)
     }
 
     pub fn reset_selected(&mut self) -> Result<()> {
@@ -373,6 +790,8 @@ impl<'a> ListState<'a> {
 
         let exercise_ind = self.selected_to_exercise_ind(selected)?;
         let exercise_name = self.app_state.reset_exercise_by_ind(exercise_ind)?;
+        // Resetting can change the position of the exercise when sorting by state.
+        self.set_sort(self.sort);
         self.update_rows();
         write!(
             self.message,
@@ -391,17 +810,9 @@ impl<'a> ListState<'a> {
             return;
         }
 
-        let is_search_result = |exercise: &Exercise| exercise.name.contains(&self.search_query);
-        let mut iter = self.app_state.exercises().iter();
-        let ind = match self.filter {
-            Filter::None => iter.position(is_search_result),
-            Filter::Done => iter
-                .filter(|exercise| exercise.done)
-                .position(is_search_result),
-            Filter::Pending => iter
-                .filter(|exercise| !exercise.done)
-                .position(is_search_result),
-        };
+        let ind = self
+            .displayed_exercises()
+            .position(|(_, exercise)| exercise.name.contains(&self.search_query));
 
         match ind {
             Some(exercise_ind) => self.scroll_state.set_selected(exercise_ind),
@@ -409,6 +820,15 @@ impl<'a> ListState<'a> {
         }
     }
 
+    pub fn selected_exercise_path(&self) -> Result<Option<&'static str>> {
+        let Some(selected) = self.scroll_state.selected() else {
+            return Ok(None);
+        };
+
+        let exercise_ind = self.selected_to_exercise_ind(selected)?;
+        Ok(Some(self.app_state.exercises()[exercise_ind].path))
+    }
+
     // Return `true` if there was something to select.
     pub fn selected_to_current_exercise(&mut self) -> Result<bool> {
         let Some(selected) = self.scroll_state.selected() else {
@@ -417,8 +837,30 @@ impl<'a> ListState<'a> {
         };
 
         let exercise_ind = self.selected_to_exercise_ind(selected)?;
+        let missing_requirements = self.app_state.missing_requirements(exercise_ind);
+        if !missing_requirements.is_empty() {
+            write!(
+                self.message,
+                "Do these exercises first: {}",
+                missing_requirements.join(", "),
+            )?;
+            return Ok(false);
+        }
         self.app_state.set_current_exercise_ind(exercise_ind)?;
 
         Ok(true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_width_of_multibyte_names() {
+        assert_eq!(max_width(4, ["intro1", "übung10"].into_iter()), 7);
+        // Wide characters take two columns.
+        assert_eq!(max_width(4, ["变量1"].into_iter()), 5);
+        assert_eq!(max_width(4, ["ab"].into_iter()), 4);
+    }
+}
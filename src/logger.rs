@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::io::{self, Write};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::Error,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Info => Self::Info,
+            LogLevel::Debug => Self::Debug,
+        }
+    }
+}
+
+// Writes the log messages of Rustlings to stderr.
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Ignore the log messages of dependencies.
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+            && metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let _ = writeln!(io::stderr(), "[{}] {}", record.level(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Logger = Logger;
+
+pub fn init(level: LogLevel) -> Result<()> {
+    log::set_logger(&LOGGER).context("Failed to set the logger")?;
+    log::set_max_level(level.into());
+
+    Ok(())
+}
@@ -1,29 +1,32 @@
 use anyhow::{Context, Result, bail};
-use app_state::StateFileStatus;
 use clap::{Parser, Subcommand};
+use crossterm::style::{Attribute, ResetColor, SetAttribute};
+use rustlings::{
+    app_state::{AppState, StateFileStatus},
+    badge::{self, BadgeFormat},
+    check_all,
+    cmd::CmdRunner,
+    config::Config,
+    dev::DevCommands,
+    export::{self, ExportFormat},
+    graph, i18n,
+    info_file::{CURRENT_FORMAT_VERSION, InfoFile},
+    init, interrupt, lint,
+    logger::{self, LogLevel},
+    run, search,
+    state::StateCommands,
+    stats::{self, StatsFormat},
+    term::{self, QueueStyle, clear_terminal, confirm_prompt, press_enter_prompt},
+    watch::{self, Feedback},
+};
 use std::{
+    env, fs,
     io::{self, IsTerminal, Write},
-    path::Path,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
     process::ExitCode,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use term::{clear_terminal, press_enter_prompt};
-
-use self::{app_state::AppState, dev::DevCommands, info_file::InfoFile};
-
-mod app_state;
-mod cargo_toml;
-mod cmd;
-mod dev;
-mod embedded;
-mod exercise;
-mod info_file;
-mod init;
-mod list;
-mod run;
-mod term;
-mod watch;
-
-const CURRENT_FORMAT_VERSION: u8 = 1;
 
 /// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
 #[derive(Parser)]
@@ -35,6 +38,40 @@ struct Args {
     /// Only use this if Rustlings fails to detect exercise file changes.
     #[arg(long)]
     manual_run: bool,
+    /// Automatically move on to the next exercise in the watch mode once the current one is done
+    #[arg(long)]
+    auto_advance: bool,
+    /// Don't list the next pending exercises in the watch mode once the current one is done
+    #[arg(long)]
+    hide_upcoming: bool,
+    /// Show a desktop notification once all exercises are done in the watch mode
+    #[arg(long)]
+    notify: bool,
+    /// Ring the terminal bell in the watch mode once if an exercise is done and twice if it is pending
+    #[arg(long)]
+    bell: bool,
+    /// Play this sound file in the watch mode once an exercise is done
+    /// (uses `paplay` on Linux, `afplay` on macOS and PowerShell on Windows)
+    #[arg(long, value_name = "FILE")]
+    sound: Option<PathBuf>,
+    /// How long to wait for further changes of a file before rerunning the exercise (in milliseconds).
+    /// Increase it if your editor saves a file multiple times in a row
+    #[arg(long, value_name = "MS", default_value_t = 200)]
+    debounce: u64,
+    /// Show the full backtrace if an exercise panics
+    #[arg(long)]
+    backtrace: bool,
+    /// Don't use colors or other styling in the output.
+    /// Styling is also disabled if the `NO_COLOR` environment variable is set or the output isn't a terminal
+    #[arg(long)]
+    no_color: bool,
+    /// The level of internal log messages written to stderr.
+    /// `debug` shows the commands which are run to check an exercise
+    #[arg(long, value_enum, default_value_t = LogLevel::Warn)]
+    log_level: LogLevel,
+    /// The maximum width of the progress bar. Defaults to the terminal width
+    #[arg(long)]
+    bar_width: Option<u16>,
 }
 
 #[derive(Subcommand)]
@@ -45,31 +82,188 @@ enum Subcommands {
     Run {
         /// The name of the exercise
         name: Option<String>,
+        /// Show the words which you changed compared to the original exercise file
+        #[arg(long)]
+        word_diff: bool,
+        /// Only print the commands which would be run to check the exercise
+        #[arg(long)]
+        print_command: bool,
+        /// Don't show long output of a failing exercise in a pager (`PAGER` or `less -R`)
+        #[arg(long)]
+        no_pager: bool,
+        /// Copy the compiled binary (and the test binary) of the exercise into this directory
+        #[arg(long, value_name = "DIR", conflicts_with = "print_command")]
+        keep_artifacts: Option<PathBuf>,
+        /// Only print `OK <NAME>` if the exercise is done instead of its output and further guidance
+        #[arg(short, long, conflicts_with_all = ["word_diff", "print_command"])]
+        quiet: bool,
+        /// Write the content of this file to the stdin of the exercise instead of an empty stdin
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// Compile and run a Rust file every time it is saved instead of running an exercise
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["name", "word_diff", "print_command", "no_pager", "keep_artifacts", "quiet"])]
+        watch_path: Option<PathBuf>,
     },
     /// Check all the exercises, marking them as done or pending accordingly.
-    CheckAll,
-    /// Reset a single exercise
+    CheckAll {
+        /// Check the exercises in a random order. The first pending exercise in this order is the next one
+        #[arg(long)]
+        shuffle: bool,
+        /// The seed of the random order to make it reproducible
+        #[arg(long, requires = "shuffle")]
+        seed: Option<u64>,
+        /// Show how long checking each exercise took
+        #[arg(short, long)]
+        verbose: bool,
+        /// Show the output of all pending exercises at the end
+        #[arg(long)]
+        show_failures: bool,
+        /// Only print the output of the pending exercises if there are any (for scripts and hooks)
+        #[arg(short, long, conflicts_with = "verbose")]
+        quiet: bool,
+        /// Only check the exercises whose files have unstaged or staged changes according to Git
+        #[arg(long)]
+        changed: bool,
+        /// Only check the exercise with this name and the ones after it
+        #[arg(long, value_name = "NAME")]
+        from: Option<String>,
+        /// Only check the exercises with this tag (like `ownership`)
+        #[arg(long)]
+        tag: Option<String>,
+        /// Stop checking further exercises once this many exercises are pending (for grading)
+        #[arg(long, value_name = "N")]
+        max_failures: Option<NonZeroUsize>,
+        /// Remove the build artifacts of all exercises first to build them from scratch
+        #[arg(long)]
+        clean: bool,
+        /// Continue in the watch mode on the first pending exercise if there is one
+        #[arg(long, conflicts_with = "quiet")]
+        then_watch: bool,
+        /// Write a JUnit XML report with a test case for each checked exercise to this file (for CI)
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+    },
+    /// Reset a single exercise or all exercises of a chapter
     Reset {
+        /// The name of the exercise
+        #[arg(required_unless_present = "chapter")]
+        name: Option<String>,
+        /// Reset all exercises in this chapter directory (like `09_strings`) after a confirmation
+        #[arg(long, conflicts_with = "name")]
+        chapter: Option<String>,
+        /// Only show the changes which would be discarded without resetting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Pick a random pending exercise (neither done nor skipped), make it the current one and run it
+    Random {
+        /// The seed of the random pick to make it reproducible
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Show the solution of an exercise. Asks for a confirmation if the exercise isn't done yet
+    Solution {
         /// The name of the exercise
         name: String,
     },
+    /// Skip an exercise for now. Skips the current exercise if the exercise name is not specified.
+    /// Skipped exercises don't block moving on to the next exercises
+    Skip {
+        /// The name of the exercise
+        name: Option<String>,
+    },
     /// Show a hint. Shows the hint of the next pending exercise if the exercise name is not specified
     Hint {
         /// The name of the exercise
         name: Option<String>,
+        /// Show the hints of all exercises
+        #[arg(long, conflicts_with = "name")]
+        all: bool,
+        /// Only show the hints of the exercises in this chapter directory (like `09_strings`)
+        #[arg(long, requires = "all")]
+        chapter: Option<String>,
+    },
+    /// Run Clippy on all exercises and show the lints without changing whether they are done.
+    /// Only lints the given exercise if its name is specified
+    Lint {
+        /// The name of the exercise
+        name: Option<String>,
+    },
+    /// Search the exercise files and hints for a term (case-insensitive)
+    Search {
+        /// The term to search for
+        term: String,
+        /// Only search the hints
+        #[arg(long, conflicts_with = "source_only")]
+        hints_only: bool,
+        /// Only search the exercise files
+        #[arg(long)]
+        source_only: bool,
+    },
+    /// Show how many exercises are done, also grouped by what is checked in them
+    Stats {
+        /// The output format
+        #[arg(long, value_enum, default_value_t = StatsFormat::Text)]
+        format: StatsFormat,
+        /// Show how many exercises were done per day instead
+        #[arg(long)]
+        history: bool,
+        /// Don't print a trailing newline with `--format oneline`, for example in a shell prompt
+        #[arg(short = 'n', long)]
+        no_newline: bool,
+    },
+    /// Export or import your progress
+    #[command(subcommand)]
+    State(StateCommands),
+    /// Export a summary of your progress
+    Export {
+        /// The format of the summary
+        #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+        format: ExportFormat,
+        /// The file to write the summary to
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Render a badge with your completion percentage, for example for a README
+    Badge {
+        /// The format of the badge
+        #[arg(long, value_enum, default_value_t = BadgeFormat::Svg)]
+        format: BadgeFormat,
+        /// The file to write the badge to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
+    /// Print the graph of the exercise requirements in the DOT format of Graphviz, colored by the exercise states
+    Graph,
     /// Commands for developing (third-party) Rustlings exercises
     #[command(subcommand)]
     Dev(DevCommands),
 }
 
 fn main() -> Result<ExitCode> {
-    let args = Args::parse();
+    let mut args = Args::parse();
 
     if cfg!(not(debug_assertions)) && Path::new("dev/rustlings-repo.txt").exists() {
         bail!("{OLD_METHOD_ERR}");
     }
 
+    interrupt::set_handler()?;
+    interrupt::set_panic_hook();
+    logger::init(args.log_level)?;
+
+    if let Some(bar_width) = args.bar_width {
+        term::set_bar_width(bar_width);
+    }
+    i18n::init()?;
+
+    if args.no_color
+        || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+        || !io::stdout().is_terminal()
+        || term::dumb_terminal()
+    {
+        term::disable_styling();
+    }
+
     'priority_cmd: {
         match args.command {
             Some(Subcommands::Init) => init::init().context("Initialization failed")?,
@@ -85,19 +279,57 @@ fn main() -> Result<ExitCode> {
         return Ok(ExitCode::FAILURE);
     }
 
-    let info_file = InfoFile::parse()?;
+    let mut info_file = InfoFile::parse()?;
 
     if info_file.format_version > CURRENT_FORMAT_VERSION {
         bail!(FORMAT_VERSION_HIGHER_ERR);
     }
 
+    if Path::new("info.toml").exists() {
+        info_file.remove_ignored_exercises()?;
+    }
+
+    let mut cmd_runner = CmdRunner::build()?;
+    cmd_runner.set_backtrace(args.backtrace);
+    let config = Config::parse()?;
+    cmd_runner.set_rustc_flags(config.rustc_flags);
+    if let Some(Subcommands::Run {
+        input: Some(input), ..
+    }) = &args.command
+    {
+        let input = fs::read(input)
+            .with_context(|| format!("Failed to read the input file {}", input.display()))?;
+        cmd_runner.set_bin_input(input);
+    }
+
     let (mut app_state, state_file_status) = AppState::new(
         info_file.exercises,
         info_file.final_message.unwrap_or_default(),
+        cmd_runner,
     )?;
+    if config.teach_mode {
+        app_state.enable_teach_mode();
+    }
+
+    let mut missing_exercises = app_state
+        .exercises()
+        .iter()
+        .filter(|exercise| exercise.missing())
+        .peekable();
+    if missing_exercises.peek().is_some() && log::log_enabled!(log::Level::Warn) {
+        eprintln!("Warning: The following exercise files are missing:");
+        for exercise in missing_exercises {
+            eprintln!("  {}", exercise.path);
+        }
+        eprintln!("Run `rustlings reset <exercise name>` to restore a missing exercise file\n");
+    }
 
     // Show the welcome message if the state file doesn't exist yet.
-    if let Some(welcome_message) = info_file.welcome_message {
+    // Don't wait for ENTER if the output isn't a terminal, e.g. when `rustlings run` is used in a script.
+    if let Some(welcome_message) = info_file
+        .welcome_message
+        .filter(|_| io::stdout().is_terminal())
+    {
         match state_file_status {
             StateFileStatus::NotRead => {
                 let mut stdout = io::stdout().lock();
@@ -114,74 +346,187 @@ fn main() -> Result<ExitCode> {
         }
     }
 
-    match args.command {
-        None => {
-            if !io::stdout().is_terminal() {
-                bail!("Unsupported or missing terminal/TTY");
+    match args.command.take() {
+        None => watch_mode(&mut app_state, &args)?,
+        Some(Subcommands::Run {
+            name,
+            word_diff,
+            print_command,
+            no_pager,
+            keep_artifacts,
+            quiet,
+            // Already passed to the command runner.
+            input: _,
+            watch_path,
+        }) => {
+            if let Some(watch_path) = watch_path {
+                watch::watch_scratch_file(
+                    app_state.cmd_runner(),
+                    &watch_path,
+                    Duration::from_millis(args.debounce),
+                )?;
+                return Ok(ExitCode::SUCCESS);
             }
 
-            let notify_exercise_names = if args.manual_run {
-                None
-            } else {
-                // For the notify event handler thread.
-                // Leaking is not a problem because the slice lives until the end of the program.
-                Some(
-                    &*app_state
-                        .exercises()
-                        .iter()
-                        .map(|exercise| exercise.name.as_bytes())
-                        .collect::<Vec<_>>()
-                        .leak(),
-                )
-            };
+            if let Some(name) = name {
+                app_state.jump_to_exercise(app_state.exercise_ind_by_name(&name)?)?;
+            }
+            return run::run(
+                &mut app_state,
+                word_diff,
+                print_command,
+                !no_pager,
+                keep_artifacts.as_deref(),
+                quiet,
+            );
+        }
+        Some(Subcommands::CheckAll {
+            shuffle,
+            seed,
+            verbose,
+            show_failures,
+            quiet,
+            changed,
+            from,
+            tag,
+            clean,
+            max_failures,
+            then_watch,
+            report,
+        }) => {
+            // Fail before checking all exercises instead of after it.
+            if then_watch {
+                ensure_terminal()?;
+            }
+
+            let exit_code = check_all::check_all(
+                &mut app_state,
+                check_all::Selection {
+                    changed,
+                    from: from.as_deref(),
+                    tag: tag.as_deref(),
+                },
+                check_all::Options {
+                    shuffle,
+                    seed,
+                    verbose,
+                    show_failures,
+                    quiet,
+                    clean,
+                    max_failures,
+                },
+            )?;
 
-            watch::watch(&mut app_state, notify_exercise_names)?;
+            if let Some(report) = report {
+                check_all::write_junit_report(&app_state, &report)?;
+            }
+
+            // Continue in the watch mode on the first pending exercise.
+            if !then_watch || app_state.current_exercise().done {
+                return Ok(exit_code);
+            }
+            watch_mode(&mut app_state, &args)?;
         }
-        Some(Subcommands::Run { name }) => {
-            if let Some(name) = name {
+        Some(Subcommands::Reset {
+            name,
+            chapter,
+            dry_run,
+        }) => match (chapter, name) {
+            (Some(chapter), _) if dry_run => {
+                reset_dry_run(&app_state, &app_state.chapter_exercise_inds(&chapter)?)?;
+            }
+            (Some(chapter), _) => reset_chapter(&mut app_state, &chapter)?,
+            (None, Some(name)) if dry_run => {
+                reset_dry_run(&app_state, &[app_state.exercise_ind_by_name(&name)?])?;
+            }
+            (None, Some(name)) => {
                 app_state.set_current_exercise_by_name(&name)?;
+                let exercise_path = app_state.reset_current_exercise()?;
+                println!("The exercise {exercise_path} has been reset");
             }
-            return run::run(&mut app_state);
-        }
-        Some(Subcommands::CheckAll) => {
-            let mut stdout = io::stdout().lock();
-            if let Some(first_pending_exercise_ind) = app_state.check_all_exercises(&mut stdout)? {
-                if app_state.current_exercise().done {
-                    app_state.set_current_exercise_ind(first_pending_exercise_ind)?;
-                }
-
-                stdout.write_all(b"\n\n")?;
-                let pending = app_state.n_pending();
-                if pending == 1 {
-                    stdout.write_all(b"One exercise pending: ")?;
-                } else {
-                    write!(
-                        stdout,
-                        "{pending}/{} exercises pending. The first: ",
-                        app_state.exercises().len(),
-                    )?;
-                }
-                app_state
-                    .current_exercise()
-                    .terminal_file_link(&mut stdout)?;
-                stdout.write_all(b"\n")?;
-
-                return Ok(ExitCode::FAILURE);
+            // Already rejected by Clap, with or without `--dry-run`.
+            (None, None) => bail!("The name of the exercise or a chapter is required"),
+        },
+        Some(Subcommands::Random { seed }) => {
+            let seed = seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |duration| duration.as_nanos() as u64)
+            });
+
+            let Some(exercise_ind) = app_state.random_pending_exercise_ind(seed) else {
+                app_state.render_final_message(&mut io::stdout().lock())?;
+                return Ok(ExitCode::SUCCESS);
+            };
+            app_state.set_current_exercise_ind(exercise_ind)?;
+            println!(
+                "Picked {} with the seed {seed}\n",
+                app_state.current_exercise().path,
+            );
+
+            return run::run(&mut app_state, false, false, true, None, false);
+        }
+        Some(Subcommands::Solution { name }) => print_solution(&app_state, &name)?,
+        Some(Subcommands::Skip { name }) => {
+            let exercise_ind = match name {
+                Some(name) => app_state.exercise_ind_by_name(&name)?,
+                None => app_state.current_exercise_ind(),
+            };
+            let skipped_exercise_path = app_state.exercises()[exercise_ind].path;
+
+            if app_state.skip_exercise(exercise_ind)? {
+                println!(
+                    "Skipped {skipped_exercise_path}\nNext exercise: {}",
+                    app_state.current_exercise().path,
+                );
             } else {
-                app_state.render_final_message(&mut stdout)?;
+                println!(
+                    "Skipped {skipped_exercise_path}\nAll other exercises are done or skipped",
+                );
             }
         }
-        Some(Subcommands::Reset { name }) => {
-            app_state.set_current_exercise_by_name(&name)?;
-            let exercise_path = app_state.reset_current_exercise()?;
-            println!("The exercise {exercise_path} has been reset");
-        }
-        Some(Subcommands::Hint { name }) => {
+        Some(Subcommands::Hint {
+            all: true, chapter, ..
+        }) => print_all_hints(&mut app_state, chapter.as_deref())?,
+        Some(Subcommands::Hint { name, .. }) => {
             if let Some(name) = name {
                 app_state.set_current_exercise_by_name(&name)?;
             }
             println!("{}", app_state.current_exercise().hint);
+            app_state.mark_hint_viewed(app_state.current_exercise_ind())?;
+        }
+        Some(Subcommands::Lint { name }) => lint::lint(&app_state, name.as_deref())?,
+        Some(Subcommands::Search {
+            term,
+            hints_only,
+            source_only,
+        }) => {
+            let scope = search::Scope {
+                source: !hints_only,
+                hints: !source_only,
+            };
+            return search::search(&app_state, &term, scope);
+        }
+        Some(Subcommands::State(state_command)) => state_command.run(&mut app_state)?,
+        Some(Subcommands::Stats {
+            format,
+            history,
+            no_newline,
+        }) => {
+            if history {
+                stats::print_history(format)?;
+            } else {
+                stats::print_stats(&app_state, format, no_newline)?;
+            }
         }
+        Some(Subcommands::Export { format, output }) => {
+            export::export(&app_state, format, &output)?;
+            println!("The summary has been exported to {}", output.display());
+        }
+        Some(Subcommands::Badge { format, output }) => {
+            badge::badge(&app_state, format, output.as_deref())?;
+        }
+        Some(Subcommands::Graph) => graph::graph(&app_state)?,
         // Handled in an earlier match.
         Some(Subcommands::Init | Subcommands::Dev(_)) => (),
     }
@@ -189,6 +534,148 @@ fn main() -> Result<ExitCode> {
     Ok(ExitCode::SUCCESS)
 }
 
+// The watch mode requires a terminal.
+fn ensure_terminal() -> Result<()> {
+    if !io::stdout().is_terminal() {
+        bail!("Unsupported or missing terminal/TTY");
+    }
+
+    Ok(())
+}
+
+// Start the watch mode on the current exercise.
+fn watch_mode(app_state: &mut AppState, args: &Args) -> Result<()> {
+    ensure_terminal()?;
+
+    if let Some(sound) = &args.sound {
+        if !sound.is_file() {
+            bail!("The sound file {} doesn't exist", sound.display());
+        }
+    }
+
+    let notify_exercise_names = if args.manual_run {
+        None
+    } else {
+        // For the notify event handler thread.
+        // Leaking is not a problem because the slice lives until the end of the program.
+        Some(
+            &*app_state
+                .exercises()
+                .iter()
+                .map(|exercise| exercise.name.as_bytes())
+                .collect::<Vec<_>>()
+                .leak(),
+        )
+    };
+
+    watch::watch(
+        app_state,
+        notify_exercise_names,
+        args.auto_advance,
+        !args.hide_upcoming,
+        args.notify,
+        &Feedback {
+            bell: args.bell,
+            sound: args.sound.clone(),
+        },
+        Duration::from_millis(args.debounce),
+    )
+}
+
+fn print_all_hints(app_state: &mut AppState, chapter: Option<&str>) -> Result<()> {
+    let exercise_inds = match chapter {
+        Some(chapter) => app_state.chapter_exercise_inds(chapter)?,
+        None => (0..app_state.exercises().len()).collect(),
+    };
+
+    let mut stdout = io::stdout().lock();
+    for (n, exercise_ind) in exercise_inds.into_iter().enumerate() {
+        if n > 0 {
+            stdout.write_all(b"\n")?;
+        }
+
+        let exercise = &app_state.exercises()[exercise_ind];
+        stdout.queue_style(SetAttribute(Attribute::Bold))?;
+        stdout.write_all(exercise.name.as_bytes())?;
+        stdout.queue_style(ResetColor)?;
+        writeln!(stdout, " ({})", exercise.path)?;
+
+        let hint = exercise.hint.trim();
+        if !hint.is_empty() {
+            stdout.write_all(hint.as_bytes())?;
+            stdout.write_all(b"\n")?;
+        }
+        app_state.mark_hint_viewed(exercise_ind)?;
+    }
+
+    Ok(())
+}
+
+fn print_solution(app_state: &AppState, name: &str) -> Result<()> {
+    let exercise_ind = app_state.exercise_ind_by_name(name)?;
+    let Some(solution) = app_state.solution_file(exercise_ind)? else {
+        bail!("The exercise {name} has no solution file");
+    };
+
+    let mut stdout = io::stdout().lock();
+    let exercise = &app_state.exercises()[exercise_ind];
+    if !exercise.done {
+        writeln!(
+            stdout,
+            "The exercise {} isn't done yet. Try its hint first: `rustlings hint {name}`\n",
+            exercise.path,
+        )?;
+
+        if !confirm_prompt(
+            &mut stdout,
+            "Show the solution anyway? This will spoil the exercise",
+        )? {
+            stdout.write_all(b"The solution hasn't been shown\n")?;
+            return Ok(());
+        }
+        stdout.write_all(b"\n")?;
+    }
+
+    stdout.write_all(&solution)?;
+
+    Ok(())
+}
+
+// Show what resetting the exercises would discard.
+fn reset_dry_run(app_state: &AppState, exercise_inds: &[usize]) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    for &exercise_ind in exercise_inds {
+        app_state.write_reset_diff(&mut stdout, exercise_ind)?;
+        stdout.write_all(b"\n")?;
+    }
+    stdout.write_all(b"Nothing has been reset because of `--dry-run`\n")?;
+
+    Ok(())
+}
+
+fn reset_chapter(app_state: &mut AppState, chapter: &str) -> Result<()> {
+    let exercise_inds = app_state.chapter_exercise_inds(chapter)?;
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(b"Your changes to the following exercises will be lost:\n")?;
+    for &exercise_ind in &exercise_inds {
+        writeln!(stdout, "  {}", app_state.exercises()[exercise_ind].path)?;
+    }
+    stdout.write_all(b"\n")?;
+
+    if !confirm_prompt(&mut stdout, "Reset them")? {
+        stdout.write_all(b"Nothing has been reset\n")?;
+        return Ok(());
+    }
+
+    for &exercise_ind in &exercise_inds {
+        app_state.reset_exercise_by_ind(exercise_ind)?;
+    }
+    writeln!(stdout, "{} exercises have been reset", exercise_inds.len())?;
+
+    Ok(())
+}
+
 const OLD_METHOD_ERR: &str =
     "You are trying to run Rustlings using the old method before version 6.
 The new method doesn't include cloning the Rustlings' repository.
@@ -1,27 +1,119 @@
-use anyhow::Result;
-use crossterm::{
-    QueueableCommand,
-    style::{Color, ResetColor, SetForegroundColor},
-};
+use anyhow::{Context, Result, bail};
+use crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
 use std::{
-    io::{self, Write},
+    fs,
+    io::{self, StdoutLock, Write},
+    path::{Path, PathBuf},
     process::ExitCode,
 };
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
-    exercise::{OUTPUT_CAPACITY, RunnableExercise, solution_link_line},
+    diff::write_word_diff,
+    exercise::{
+        OUTPUT_CAPACITY, PendingReason, RunnableExercise, hint_required_line, solution_link_line,
+    },
+    i18n,
+    term::{QueueStyle, write_paged},
 };
 
-pub fn run(app_state: &mut AppState) -> Result<ExitCode> {
+// Copy the binaries of the current exercise into the directory `dir` to inspect them.
+// The test binary is only copied for exercises with tests.
+fn copy_artifacts(app_state: &AppState, dir: &Path) -> Result<Vec<PathBuf>> {
     let exercise = app_state.current_exercise();
+    let cmd_runner = app_state.cmd_runner();
+
+    let mut artifacts = vec![cmd_runner.debug_bin_path(exercise.name)];
+    if exercise.test {
+        artifacts.extend(cmd_runner.build_test_bin(exercise.name)?);
+    }
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create the directory {}", dir.display()))?;
+
+    let mut artifact_paths = Vec::with_capacity(artifacts.len());
+    for artifact in artifacts {
+        let Some(file_name) = artifact.file_name() else {
+            continue;
+        };
+        let artifact_path = dir.join(file_name);
+        fs::copy(&artifact, &artifact_path).with_context(|| {
+            format!(
+                "Failed to copy {} to {}",
+                artifact.display(),
+                artifact_path.display(),
+            )
+        })?;
+        artifact_paths.push(artifact_path);
+    }
+
+    Ok(artifact_paths)
+}
+
+fn write_artifact_paths(stdout: &mut StdoutLock, artifact_paths: &[PathBuf]) -> io::Result<()> {
+    for artifact_path in artifact_paths {
+        writeln!(stdout, "Kept the binary {}", artifact_path.display())?;
+    }
+    stdout.write_all(b"\n")
+}
+
+pub fn run(
+    app_state: &mut AppState,
+    word_diff: bool,
+    print_command: bool,
+    pager: bool,
+    keep_artifacts: Option<&Path>,
+    quiet: bool,
+) -> Result<ExitCode> {
+    let exercise = app_state.current_exercise();
+    if exercise.missing() {
+        bail!(
+            "The exercise file {} is missing.\nRun `rustlings reset {}` to restore it",
+            exercise.path,
+            exercise.name,
+        );
+    }
+
     let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
-    let success = exercise.run_exercise(Some(&mut output), app_state.cmd_runner())?;
+
+    if print_command {
+        exercise.run_exercise(Some(&mut output), &app_state.cmd_runner().print_only())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut pending_reason =
+        exercise.exercise_pending_reason(Some(&mut output), app_state.cmd_runner())?;
+    if pending_reason.is_none() && app_state.hint_required(app_state.current_exercise_ind()) {
+        hint_required_line(&mut output, exercise.name)?;
+        pending_reason = Some(PendingReason::HintNotViewed);
+    }
 
     let mut stdout = io::stdout().lock();
-    stdout.write_all(&output)?;
 
-    if !success {
+    // Only copy the binaries after a successful build. Otherwise, they could be old binaries.
+    // The build doesn't run if the exercise fails the checks of its source file.
+    let built = !matches!(
+        pending_reason,
+        Some(
+            PendingReason::CompilationFailed
+                | PendingReason::StyleViolations
+                | PendingReason::ForbiddenPattern
+        ),
+    );
+    let keep_artifacts = keep_artifacts
+        .filter(|_| built)
+        .map(|dir| copy_artifacts(app_state, dir));
+
+    if pending_reason.is_some() {
+        if pager {
+            write_paged(&mut stdout, &output)?;
+        } else {
+            stdout.write_all(&output)?;
+        }
+        if let Some(artifact_paths) = keep_artifacts {
+            write_artifact_paths(&mut stdout, &artifact_paths?)?;
+        }
+
         app_state.set_pending(app_state.current_exercise_ind())?;
 
         stdout.write_all(b"Ran ")?;
@@ -33,12 +125,43 @@ pub fn run(app_state: &mut AppState) -> Result<ExitCode> {
         return Ok(ExitCode::FAILURE);
     }
 
-    stdout.queue(SetForegroundColor(Color::Green))?;
-    stdout.write_all("✓ Successfully ran ".as_bytes())?;
-    stdout.write_all(exercise.path.as_bytes())?;
-    stdout.queue(ResetColor)?;
+    if quiet {
+        // Artifacts are still kept, but their paths aren't printed.
+        keep_artifacts.transpose()?;
+        writeln!(stdout, "OK {}", exercise.name)?;
+        // The next exercise isn't shown.
+        let _ = app_state.done_current_exercise::<false>(&mut stdout)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    stdout.write_all(&output)?;
+    if let Some(artifact_paths) = keep_artifacts {
+        write_artifact_paths(&mut stdout, &artifact_paths?)?;
+    }
+    stdout.queue_style(SetForegroundColor(Color::Green))?;
+    stdout.write_all(i18n::tr_args("run_success", &[("path", exercise.path)]).as_bytes())?;
+    stdout.queue_style(ResetColor)?;
     stdout.write_all(b"\n")?;
 
+    if word_diff {
+        let original = app_state.original_exercise_file(app_state.current_exercise_ind())?;
+        let current = fs::read(exercise.path)
+            .with_context(|| format!("Failed to read the exercise file {}", exercise.path))?;
+
+        stdout.write_all(b"\n")?;
+        stdout.queue_style(SetAttribute(Attribute::Underlined))?;
+        stdout.write_all(b"Your changes")?;
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b"\n")?;
+        if !write_word_diff(
+            &mut stdout,
+            &String::from_utf8_lossy(&original),
+            &String::from_utf8_lossy(&current),
+        )? {
+            stdout.write_all(b"No changes compared to the original exercise file\n")?;
+        }
+    }
+
     if let Some(solution_path) = app_state.current_solution_path()? {
         stdout.write_all(b"\n")?;
         solution_link_line(&mut stdout, &solution_path)?;
@@ -0,0 +1,138 @@
+use anyhow::Result;
+use crossterm::style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor};
+use std::{
+    io::{self, StdoutLock, Write},
+    process::ExitCode,
+};
+
+use crate::{app_state::AppState, exercise::read_source_lossy, term::QueueStyle};
+
+/// Where to search for the term.
+#[derive(Clone, Copy)]
+pub struct Scope {
+    pub source: bool,
+    pub hints: bool,
+}
+
+// Byte ranges of the ASCII case-insensitive matches of `term` in `line`.
+fn match_ranges(line: &str, term: &str) -> Vec<(usize, usize)> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+
+    let line = line.to_ascii_lowercase();
+    let term = term.to_ascii_lowercase();
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while let Some(ind) = line[start..].find(&term) {
+        let match_start = start + ind;
+        let match_end = match_start + term.len();
+        ranges.push((match_start, match_end));
+        start = match_end;
+    }
+
+    ranges
+}
+
+fn write_line(
+    stdout: &mut StdoutLock,
+    prefix: &str,
+    line: &str,
+    ranges: &[(usize, usize)],
+) -> io::Result<()> {
+    stdout.queue_style(SetForegroundColor(Color::Blue))?;
+    write!(stdout, "  {prefix}: ")?;
+    stdout.queue_style(ResetColor)?;
+
+    let mut last_end = 0;
+    for &(start, end) in ranges {
+        stdout.write_all(&line.as_bytes()[last_end..start])?;
+        stdout
+            .queue_style(SetForegroundColor(Color::Red))?
+            .queue_style(SetAttribute(Attribute::Bold))?;
+        stdout.write_all(&line.as_bytes()[start..end])?;
+        stdout.queue_style(ResetColor)?;
+        last_end = end;
+    }
+    stdout.write_all(line[last_end..].trim_end().as_bytes())?;
+
+    stdout.write_all(b"\n")
+}
+
+/// Search the exercise files and hints for a term (ASCII case-insensitive).
+pub fn search(app_state: &AppState, term: &str, scope: Scope) -> Result<ExitCode> {
+    let mut stdout = io::stdout().lock();
+    let mut n_matching_exercises = 0;
+
+    for exercise in app_state.exercises() {
+        let source = if scope.source && !exercise.missing() {
+            read_source_lossy(exercise.path)?
+        } else {
+            String::new()
+        };
+
+        let source_matches = source
+            .lines()
+            .zip(1..)
+            .filter_map(|(line, line_num)| {
+                let ranges = match_ranges(line, term);
+                (!ranges.is_empty()).then_some((line, line_num, ranges))
+            })
+            .collect::<Vec<_>>();
+
+        let hint_matches = if scope.hints {
+            exercise
+                .hint
+                .lines()
+                .filter_map(|line| {
+                    let ranges = match_ranges(line, term);
+                    (!ranges.is_empty()).then_some((line, ranges))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if source_matches.is_empty() && hint_matches.is_empty() {
+            continue;
+        }
+        n_matching_exercises += 1;
+
+        stdout.queue_style(SetAttribute(Attribute::Bold))?;
+        stdout.write_all(exercise.name.as_bytes())?;
+        stdout.queue_style(ResetColor)?;
+        stdout.write_all(b" (")?;
+        exercise.terminal_file_link(&mut stdout)?;
+        stdout.write_all(b")\n")?;
+
+        for (line, line_num, ranges) in source_matches {
+            write_line(&mut stdout, &line_num.to_string(), line, &ranges)?;
+        }
+
+        for (line, ranges) in hint_matches {
+            write_line(&mut stdout, "hint", line, &ranges)?;
+        }
+    }
+
+    if n_matching_exercises == 0 {
+        writeln!(stdout, "No exercise matches `{term}`")?;
+        return Ok(ExitCode::FAILURE);
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        assert_eq!(match_ranges("", "vec"), []);
+        assert_eq!(match_ranges("vec", ""), []);
+        assert_eq!(match_ranges("let v = Vec::new();", "vec"), [(8, 11)]);
+        assert_eq!(match_ranges("vec vec", "VEC"), [(0, 3), (4, 7)]);
+        assert_eq!(match_ranges("aaa", "aa"), [(0, 2)]);
+    }
+}
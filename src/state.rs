@@ -0,0 +1,51 @@
+use anyhow::Result;
+use clap::Subcommand;
+use std::path::PathBuf;
+
+use crate::app_state::AppState;
+
+#[derive(Subcommand)]
+pub enum StateCommands {
+    /// Export your progress to a file, for example to continue on another machine
+    Export {
+        /// The file to write the progress to
+        file: PathBuf,
+    },
+    /// Import your progress from a file which was exported by `rustlings state export`
+    Import {
+        /// The file to read the progress from
+        file: PathBuf,
+    },
+}
+
+impl StateCommands {
+    pub fn run(self, app_state: &mut AppState) -> Result<()> {
+        match self {
+            Self::Export { file } => {
+                app_state.export_state(&file)?;
+                println!("The progress has been exported to {}", file.display());
+            }
+            Self::Import { file } => {
+                let unknown_exercises = app_state.import_state(&file)?;
+                if !unknown_exercises.is_empty() {
+                    eprintln!(
+                        "Warning: The following done exercises in the imported file don't exist:"
+                    );
+                    for name in unknown_exercises {
+                        eprintln!("  {name}");
+                    }
+                    eprintln!();
+                }
+
+                println!(
+                    "The progress has been imported from {}. {}/{} exercises are done",
+                    file.display(),
+                    app_state.n_done(),
+                    app_state.exercises().len(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+
+use crate::{app_state::AppState, exercise::Exercise, history};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StatsFormat {
+    Text,
+    Json,
+    /// Short line without colors for shell prompts and status bars.
+    Oneline,
+}
+
+#[derive(Default, Serialize)]
+struct Count {
+    done: u16,
+    total: u16,
+}
+
+impl Count {
+    fn add(&mut self, exercise: &Exercise) {
+        self.total += 1;
+        if exercise.done {
+            self.done += 1;
+        }
+    }
+}
+
+/// Counts by what is checked in an exercise.
+#[derive(Default, Serialize)]
+struct ModeCounts {
+    tests: Count,
+    strict_clippy: Count,
+    compile_and_run: Count,
+}
+
+#[derive(Serialize)]
+struct Stats {
+    done: u16,
+    pending: u16,
+    /// Part of the pending exercises.
+    skipped: usize,
+    total: u16,
+    percentage: f64,
+    modes: ModeCounts,
+}
+
+fn stats(app_state: &AppState) -> Stats {
+    let mut modes = ModeCounts::default();
+    for exercise in app_state.exercises() {
+        let count = if exercise.test {
+            &mut modes.tests
+        } else if exercise.strict_clippy {
+            &mut modes.strict_clippy
+        } else {
+            &mut modes.compile_and_run
+        };
+        count.add(exercise);
+    }
+
+    Stats {
+        done: app_state.n_done(),
+        pending: app_state.n_pending(),
+        skipped: app_state.n_skipped(),
+        total: app_state.exercises().len() as u16,
+        percentage: app_state.completion_percentage(),
+        modes,
+    }
+}
+
+/// Print a summary of the done and pending exercises.
+/// `no_newline` omits the trailing newline of the `oneline` format.
+pub fn print_stats(app_state: &AppState, format: StatsFormat, no_newline: bool) -> Result<()> {
+    let stats = stats(app_state);
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        StatsFormat::Text => {
+            writeln!(
+                stdout,
+                "Done: {}/{} ({:.1}%)\nPending: {}",
+                stats.done, stats.total, stats.percentage, stats.pending,
+            )?;
+            if stats.skipped > 0 {
+                writeln!(stdout, "Skipped: {}", stats.skipped)?;
+            }
+            stdout.write_all(b"\n")?;
+
+            for (name, count) in [
+                ("Tests", &stats.modes.tests),
+                ("Strict Clippy", &stats.modes.strict_clippy),
+                ("Compile and run", &stats.modes.compile_and_run),
+            ] {
+                if count.total > 0 {
+                    writeln!(stdout, "{name}: {}/{}", count.done, count.total)?;
+                }
+            }
+        }
+        StatsFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &stats)
+                .context("Failed to serialize the stats to JSON")?;
+            stdout.write_all(b"\n")?;
+        }
+        StatsFormat::Oneline => {
+            write!(
+                stdout,
+                "rustlings {}/{} ({:.1}%)",
+                stats.done, stats.total, stats.percentage,
+            )?;
+            if !no_newline {
+                stdout.write_all(b"\n")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print how many exercises were done per day according to the history.
+pub fn print_history(format: StatsFormat) -> Result<()> {
+    let done_per_day = history::done_per_day()?;
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        StatsFormat::Text => {
+            if done_per_day.is_empty() {
+                stdout.write_all(b"No exercises have been done since the history was started\n")?;
+            }
+
+            for (day, n_done) in &done_per_day {
+                writeln!(stdout, "{day}: {n_done}")?;
+            }
+        }
+        StatsFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &done_per_day)
+                .context("Failed to serialize the history to JSON")?;
+            stdout.write_all(b"\n")?;
+        }
+        StatsFormat::Oneline => {
+            for (ind, (day, n_done)) in done_per_day.iter().enumerate() {
+                if ind > 0 {
+                    stdout.write_all(b", ")?;
+                }
+                write!(stdout, "{day}: {n_done}")?;
+            }
+            stdout.write_all(b"\n")?;
+        }
+    }
+
+    Ok(())
+}
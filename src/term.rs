@@ -2,14 +2,67 @@ use crossterm::{
     Command, QueueableCommand,
     cursor::MoveTo,
     style::{Attribute, Color, ResetColor, SetAttribute, SetForegroundColor},
-    terminal::{Clear, ClearType},
+    terminal::{self, Clear, ClearType},
 };
 use std::{
-    fmt, fs,
-    io::{self, BufRead, StdoutLock, Write},
+    env, fmt, fs,
+    io::{self, BufRead, IsTerminal, StdoutLock, Write},
+    process::{Command as ProcessCommand, Stdio},
+    sync::atomic::{AtomicBool, AtomicU16, Ordering::Relaxed},
+    time::{Duration, Instant},
 };
 
-use crate::app_state::CheckProgress;
+use unicode_width::UnicodeWidthChar;
+
+use crate::{app_state::CheckProgress, i18n};
+
+static STYLING: AtomicBool = AtomicBool::new(true);
+
+// How often the number of checked exercises is printed if the progress can't be drawn.
+const PLAIN_PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// `TERM=dumb` is set by terminals which don't support escape sequences (e.g. some IDE consoles).
+pub fn dumb_terminal() -> bool {
+    env::var_os("TERM").is_some_and(|term| term == "dumb")
+}
+
+/// Stdout is a terminal which supports escape sequences for moving the cursor and clearing.
+pub fn escape_sequences_supported(stdout: &impl IsTerminal) -> bool {
+    stdout.is_terminal() && !dumb_terminal()
+}
+
+/// Disable colors, text attributes and links.
+/// Used if stdout isn't a terminal, `NO_COLOR` is set or `--no-color` is passed.
+pub fn disable_styling() {
+    STYLING.store(false, Relaxed);
+}
+
+#[inline]
+pub fn styling() -> bool {
+    STYLING.load(Relaxed)
+}
+
+/// Like `QueueableCommand::queue`, but skips the command if styling is disabled.
+/// Only use it for colors and text attributes, not for cursor movements.
+pub trait QueueStyle: Write + Sized {
+    fn queue_style(&mut self, command: impl Command) -> io::Result<&mut Self> {
+        if styling() {
+            self.queue(command)
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+impl<W: Write> QueueStyle for W {}
+
+// 0 means using the full terminal width.
+static BAR_WIDTH: AtomicU16 = AtomicU16::new(0);
+
+/// Limit the width of the progress bar (`--bar-width`).
+pub fn set_bar_width(width: u16) {
+    BAR_WIDTH.store(width, Relaxed);
+}
 
 pub struct MaxLenWriter<'a, 'lock> {
     pub stdout: &'a mut StdoutLock<'lock>,
@@ -51,17 +104,18 @@ impl<'lock> CountedWrite<'lock> for MaxLenWriter<'_, 'lock> {
     }
 
     fn write_str(&mut self, unicode: &str) -> io::Result<()> {
-        if let Some((ind, c)) = unicode
-            .char_indices()
-            .take(self.max_len.saturating_sub(self.len))
-            .last()
-        {
-            self.stdout
-                .write_all(&unicode.as_bytes()[..ind + c.len_utf8()])?;
-            self.len += ind + 1;
+        // Count the display width of the characters instead of their bytes.
+        let mut end = 0;
+        for (ind, c) in unicode.char_indices() {
+            let width = c.width().unwrap_or(0);
+            if self.len + width > self.max_len {
+                break;
+            }
+            self.len += width;
+            end = ind + c.len_utf8();
         }
 
-        Ok(())
+        self.stdout.write_all(&unicode.as_bytes()[..end])
     }
 
     #[inline]
@@ -87,9 +141,38 @@ impl<'a> CountedWrite<'a> for StdoutLock<'a> {
     }
 }
 
+// The number of checked exercises.
+// Exercises which aren't in the check order (like with `check-all --changed`) stay unchecked
+// and don't count towards the total.
+fn n_checked(progresses: &[CheckProgress]) -> usize {
+    progresses
+        .iter()
+        .filter(|progress| matches!(progress, CheckProgress::Done | CheckProgress::Pending))
+        .count()
+}
+
+// Write the number of checked exercises out of the ones to check.
+fn write_checked_count(
+    writer: &mut impl Write,
+    progresses: &[CheckProgress],
+    n_to_check: usize,
+) -> io::Result<()> {
+    let n_checked = n_checked(progresses);
+    debug_assert!(n_checked <= n_to_check);
+
+    write!(writer, "{n_checked}/{n_to_check}")
+}
+
 pub struct CheckProgressVisualizer<'a, 'lock> {
     stdout: &'a mut StdoutLock<'lock>,
     n_cols: usize,
+    // The number of exercises in the check order which can be less than the number of progresses.
+    n_to_check: usize,
+    // Stdout isn't a terminal, the terminal is dumb or the progress is hidden. Don't draw the progress.
+    plain: bool,
+    // When the number of checked exercises was last printed in the plain mode.
+    // `None` if the progress is hidden.
+    last_plain_progress: Option<Instant>,
 }
 
 impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
@@ -97,53 +180,123 @@ impl<'a, 'lock> CheckProgressVisualizer<'a, 'lock> {
     const DONE_COLOR: Color = Color::Green;
     const PENDING_COLOR: Color = Color::Red;
 
-    pub fn build(stdout: &'a mut StdoutLock<'lock>, term_width: u16) -> io::Result<Self> {
+    pub fn build(
+        stdout: &'a mut StdoutLock<'lock>,
+        term_width: u16,
+        n_to_check: usize,
+    ) -> io::Result<Self> {
         clear_terminal(stdout)?;
-        stdout.write_all("Checking all exercises…\n".as_bytes())?;
+        stdout.write_all(i18n::tr("check_all_checking").as_bytes())?;
+        stdout.write_all(b"\n")?;
+
+        if !escape_sequences_supported(stdout) {
+            stdout.flush()?;
+
+            return Ok(Self {
+                stdout,
+                n_cols: 0,
+                n_to_check,
+                plain: true,
+                last_plain_progress: Some(Instant::now()),
+            });
+        }
 
         // Legend
         stdout.write_all(b"Color of exercise number: ")?;
-        stdout.queue(SetForegroundColor(Self::CHECKING_COLOR))?;
+        stdout.queue_style(SetForegroundColor(Self::CHECKING_COLOR))?;
         stdout.write_all(b"Checking")?;
-        stdout.queue(ResetColor)?;
+        stdout.queue_style(ResetColor)?;
         stdout.write_all(b" - ")?;
-        stdout.queue(SetForegroundColor(Self::DONE_COLOR))?;
+        stdout.queue_style(SetForegroundColor(Self::DONE_COLOR))?;
         stdout.write_all(b"Done")?;
-        stdout.queue(ResetColor)?;
+        stdout.queue_style(ResetColor)?;
         stdout.write_all(b" - ")?;
-        stdout.queue(SetForegroundColor(Self::PENDING_COLOR))?;
+        stdout.queue_style(SetForegroundColor(Self::PENDING_COLOR))?;
         stdout.write_all(b"Pending")?;
-        stdout.queue(ResetColor)?;
+        stdout.queue_style(ResetColor)?;
         stdout.write_all(b"\n")?;
 
         // Exercise numbers with up to 3 digits.
         // +1 because the last column doesn't end with a whitespace.
         let n_cols = usize::from(term_width + 1) / 4;
 
-        Ok(Self { stdout, n_cols })
+        Ok(Self {
+            stdout,
+            n_cols,
+            n_to_check,
+            plain: false,
+            last_plain_progress: None,
+        })
+    }
+
+    /// Don't print anything while checking.
+    pub fn hidden(stdout: &'a mut StdoutLock<'lock>) -> Self {
+        Self {
+            stdout,
+            n_cols: 0,
+            n_to_check: 0,
+            plain: true,
+            last_plain_progress: None,
+        }
+    }
+
+    // Print a line with the number of checked exercises once in a while instead of drawing the
+    // progress. Keeps logs (e.g. in CI) free of escape sequences while showing that it isn't hanging.
+    fn update_plain(&mut self, progresses: &[CheckProgress]) -> io::Result<()> {
+        let Some(last_plain_progress) = &mut self.last_plain_progress else {
+            return Ok(());
+        };
+        if last_plain_progress.elapsed() < PLAIN_PROGRESS_INTERVAL {
+            return Ok(());
+        }
+        *last_plain_progress = Instant::now();
+
+        let checked = n_checked(progresses).to_string();
+        let total = self.n_to_check.to_string();
+        self.stdout.write_all(
+            i18n::tr_args(
+                "check_all_checked",
+                &[("checked", &checked), ("total", &total)],
+            )
+            .as_bytes(),
+        )?;
+        self.stdout.write_all(b"\n")?;
+        self.stdout.flush()
     }
 
     pub fn update(&mut self, progresses: &[CheckProgress]) -> io::Result<()> {
+        if self.plain {
+            return self.update_plain(progresses);
+        }
+
         self.stdout.queue(MoveTo(0, 2))?;
 
+        if self.n_cols == 0 {
+            // Too narrow for the exercise numbers. Only show the number of checked exercises.
+            write_checked_count(self.stdout, progresses, self.n_to_check)?;
+            return self.stdout.flush();
+        }
+
         let mut exercise_num = 1;
         for exercise_progress in progresses {
             match exercise_progress {
                 CheckProgress::None => (),
                 CheckProgress::Checking => {
                     self.stdout
-                        .queue(SetForegroundColor(Self::CHECKING_COLOR))?;
+                        .queue_style(SetForegroundColor(Self::CHECKING_COLOR))?;
                 }
                 CheckProgress::Done => {
-                    self.stdout.queue(SetForegroundColor(Self::DONE_COLOR))?;
+                    self.stdout
+                        .queue_style(SetForegroundColor(Self::DONE_COLOR))?;
                 }
                 CheckProgress::Pending => {
-                    self.stdout.queue(SetForegroundColor(Self::PENDING_COLOR))?;
+                    self.stdout
+                        .queue_style(SetForegroundColor(Self::PENDING_COLOR))?;
                 }
             }
 
             write!(self.stdout, "{exercise_num:<3}")?;
-            self.stdout.queue(ResetColor)?;
+            self.stdout.queue_style(ResetColor)?;
 
             if exercise_num != progresses.len() {
                 if exercise_num % self.n_cols == 0 {
@@ -169,6 +322,11 @@ pub fn progress_bar<'a>(
     debug_assert!(total <= 999);
     debug_assert!(progress <= total);
 
+    let term_width = match BAR_WIDTH.load(Relaxed) {
+        0 => term_width,
+        bar_width => bar_width.min(term_width),
+    };
+
     const PREFIX: &[u8] = b"Progress: [";
     const PREFIX_WIDTH: u16 = PREFIX.len() as u16;
     const POSTFIX_WIDTH: u16 = "] xxx/xxx".len() as u16;
@@ -185,9 +343,10 @@ pub fn progress_bar<'a>(
     stdout.write_all(PREFIX)?;
 
     let width = term_width - WRAPPER_WIDTH;
-    let filled = (width * progress) / total;
+    // An empty set of exercises has nothing to fill.
+    let filled = (width * progress).checked_div(total).unwrap_or(0);
 
-    stdout.queue(SetForegroundColor(Color::Green))?;
+    stdout.queue_style(SetForegroundColor(Color::Green))?;
     for _ in 0..filled {
         stdout.write_all(b"#")?;
     }
@@ -199,18 +358,23 @@ pub fn progress_bar<'a>(
     let width_minus_filled = width - filled;
     if width_minus_filled > 1 {
         let red_part_width = width_minus_filled - 1;
-        stdout.queue(SetForegroundColor(Color::Red))?;
+        stdout.queue_style(SetForegroundColor(Color::Red))?;
         for _ in 0..red_part_width {
             stdout.write_all(b"-")?;
         }
     }
 
-    stdout.queue(SetForegroundColor(Color::Reset))?;
+    stdout.queue_style(SetForegroundColor(Color::Reset))?;
 
     write!(stdout, "] {progress:>3}/{total}")
 }
 
 pub fn clear_terminal(stdout: &mut StdoutLock) -> io::Result<()> {
+    // Don't write escape sequences to a file, pipe or dumb terminal.
+    if !escape_sequences_supported(stdout) {
+        return Ok(());
+    }
+
     stdout
         .queue(MoveTo(0, 0))?
         .queue(Clear(ClearType::All))?
@@ -224,6 +388,68 @@ pub fn press_enter_prompt(stdout: &mut StdoutLock) -> io::Result<()> {
     stdout.write_all(b"\n")
 }
 
+/// Ask a yes/no question and wait for the answer followed by ENTER.
+/// Anything other than `y` or `yes` counts as no.
+pub fn confirm_prompt(stdout: &mut StdoutLock, question: &str) -> io::Result<bool> {
+    write!(stdout, "{question} (y/n)? ")?;
+    stdout.flush()?;
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer)? == 0 {
+        // EOF without a newline.
+        stdout.write_all(b"\n")?;
+    }
+    let answer = answer.trim();
+
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Write the output through a pager if stdout is a terminal and the output doesn't fit into it.
+/// The pager is taken from the `PAGER` environment variable and defaults to `less -R`.
+/// Falls back to writing the output directly if the pager can't be started.
+pub fn write_paged(stdout: &mut StdoutLock, output: &[u8]) -> io::Result<()> {
+    let fits = || {
+        terminal::size().is_ok_and(|(_, height)| {
+            // -1 for the line printed after the output.
+            output.iter().filter(|&&c| c == b'\n').count() < usize::from(height.saturating_sub(1))
+        })
+    };
+    if !stdout.is_terminal() || fits() || !page(stdout, output)? {
+        return stdout.write_all(output);
+    }
+
+    Ok(())
+}
+
+/// Show the output in the pager from the `PAGER` environment variable (`less -R` by default) and
+/// wait until the user quits it.
+/// Returns `false` without writing anything if the pager can't be started or `PAGER` is empty.
+pub fn page(stdout: &mut StdoutLock, output: &[u8]) -> io::Result<bool> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+    let mut pager_args = pager.split_ascii_whitespace();
+    let Some(pager_program) = pager_args.next() else {
+        // An empty `PAGER` disables paging.
+        return Ok(false);
+    };
+
+    stdout.flush()?;
+    let Ok(mut child) = ProcessCommand::new(pager_program)
+        .args(pager_args)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        return Ok(false);
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The user can quit the pager before reading everything.
+        let _ = stdin.write_all(output);
+    }
+    child.wait()?;
+
+    Ok(true)
+}
+
 /// Canonicalize, convert to string and remove verbatim part on Windows.
 pub fn canonicalize(path: &str) -> Option<String> {
     fs::canonicalize(path)
@@ -247,10 +473,14 @@ pub fn terminal_file_link<'a>(
     canonical_path: &str,
     color: Color,
 ) -> io::Result<()> {
+    if !styling() {
+        return writer.write_str(path);
+    }
+
     writer
         .stdout()
-        .queue(SetForegroundColor(color))?
-        .queue(SetAttribute(Attribute::Underlined))?;
+        .queue_style(SetForegroundColor(color))?
+        .queue_style(SetAttribute(Attribute::Underlined))?;
     writer.stdout().write_all(b"\x1b]8;;file://")?;
     writer.stdout().write_all(canonical_path.as_bytes())?;
     writer.stdout().write_all(b"\x1b\\")?;
@@ -259,13 +489,17 @@ pub fn terminal_file_link<'a>(
     writer.stdout().write_all(b"\x1b]8;;\x1b\\")?;
     writer
         .stdout()
-        .queue(SetForegroundColor(Color::Reset))?
-        .queue(SetAttribute(Attribute::NoUnderline))?;
+        .queue_style(SetForegroundColor(Color::Reset))?
+        .queue_style(SetAttribute(Attribute::NoUnderline))?;
 
     Ok(())
 }
 
 pub fn write_ansi(output: &mut Vec<u8>, command: impl Command) {
+    if !styling() {
+        return;
+    }
+
     struct FmtWriter<'a>(&'a mut Vec<u8>);
 
     impl fmt::Write for FmtWriter<'_> {
@@ -277,3 +511,26 @@ pub fn write_ansi(output: &mut Vec<u8>, command: impl Command) {
 
     let _ = command.write_ansi(&mut FmtWriter(output));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_count_of_subset() {
+        let checked_count = |progresses: &[CheckProgress], n_to_check| {
+            let mut output = Vec::new();
+            write_checked_count(&mut output, progresses, n_to_check).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        // Only the exercises 1 and 3 are in the check order.
+        let mut progresses = [CheckProgress::None; 5];
+        assert_eq!(checked_count(&progresses, 2), "0/2");
+        progresses[1] = CheckProgress::Checking;
+        assert_eq!(checked_count(&progresses, 2), "0/2");
+        progresses[1] = CheckProgress::Done;
+        progresses[3] = CheckProgress::Pending;
+        assert_eq!(checked_count(&progresses, 2), "2/2");
+    }
+}
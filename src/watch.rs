@@ -1,7 +1,7 @@
 use anyhow::{Error, Result};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
-    io::{self, Write},
+    io::{self, StdoutLock, Write},
     path::Path,
     sync::{
         atomic::{AtomicBool, Ordering::Relaxed},
@@ -12,15 +12,20 @@ use std::{
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
-    list,
+    interrupt, list,
 };
 
 use self::{notify_event::NotifyEventHandler, state::WatchState, terminal_event::InputEvent};
 
+mod desktop_notification;
+mod feedback;
 mod notify_event;
+mod scratch;
 mod state;
 mod terminal_event;
 
+pub use self::{feedback::Feedback, scratch::watch_scratch_file};
+
 static EXERCISE_RUNNING: AtomicBool = AtomicBool::new(false);
 
 // Private unit type to force using the constructor function.
@@ -59,9 +64,39 @@ enum WatchExit {
     List,
 }
 
+// Exit the watch mode once all exercises are done.
+fn all_done(notify: bool) -> WatchExit {
+    if notify {
+        desktop_notification::notify_all_done();
+    }
+
+    WatchExit::Shutdown
+}
+
+// Move on to the next pending exercises as long as the current one is done.
+fn advance_while_done(
+    watch_state: &mut WatchState,
+    stdout: &mut StdoutLock,
+) -> Result<ExercisesProgress> {
+    while watch_state.should_auto_advance() {
+        match watch_state.next_exercise(stdout)? {
+            ExercisesProgress::AllDone => return Ok(ExercisesProgress::AllDone),
+            ExercisesProgress::NewPending => watch_state.run_current_exercise(stdout)?,
+            ExercisesProgress::CurrentPending => break,
+        }
+    }
+
+    Ok(ExercisesProgress::CurrentPending)
+}
+
 fn run_watch(
     app_state: &mut AppState,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    auto_advance: bool,
+    show_upcoming: bool,
+    notify: bool,
+    feedback: &Feedback,
+    debounce: Duration,
 ) -> Result<WatchExit> {
     let (watch_event_sender, watch_event_receiver) = channel();
 
@@ -70,7 +105,7 @@ fn run_watch(
     // Otherwise, the file watcher exits.
     let _watcher_guard = if let Some(exercise_names) = notify_exercise_names {
         let notify_event_handler =
-            NotifyEventHandler::build(watch_event_sender.clone(), exercise_names)?;
+            NotifyEventHandler::build(watch_event_sender.clone(), exercise_names, debounce)?;
 
         let mut watcher = RecommendedWatcher::new(
             notify_event_handler,
@@ -90,15 +125,25 @@ fn run_watch(
         None
     };
 
-    let mut watch_state = WatchState::build(app_state, watch_event_sender, manual_run)?;
+    let mut watch_state = WatchState::build(
+        app_state,
+        watch_event_sender,
+        manual_run,
+        auto_advance,
+        show_upcoming,
+        feedback,
+    )?;
     let mut stdout = io::stdout().lock();
 
     watch_state.run_current_exercise(&mut stdout)?;
+    if let ExercisesProgress::AllDone = advance_while_done(&mut watch_state, &mut stdout)? {
+        return Ok(all_done(notify));
+    }
 
     while let Ok(event) = watch_event_receiver.recv() {
         match event {
             WatchEvent::Input(InputEvent::Next) => match watch_state.next_exercise(&mut stdout)? {
-                ExercisesProgress::AllDone => break,
+                ExercisesProgress::AllDone => return Ok(all_done(notify)),
                 ExercisesProgress::NewPending => watch_state.run_current_exercise(&mut stdout)?,
                 ExercisesProgress::CurrentPending => (),
             },
@@ -108,11 +153,12 @@ fn run_watch(
             WatchEvent::Input(InputEvent::CheckAll) => match watch_state
                 .check_all_exercises(&mut stdout)?
             {
-                ExercisesProgress::AllDone => break,
+                ExercisesProgress::AllDone => return Ok(all_done(notify)),
                 ExercisesProgress::NewPending => watch_state.run_current_exercise(&mut stdout)?,
                 ExercisesProgress::CurrentPending => watch_state.render(&mut stdout)?,
             },
             WatchEvent::Input(InputEvent::Reset) => watch_state.reset_exercise(&mut stdout)?,
+            WatchEvent::Input(InputEvent::Output) => watch_state.page_output(&mut stdout)?,
             WatchEvent::Input(InputEvent::Quit) => {
                 stdout.write_all(QUIT_MSG)?;
                 break;
@@ -128,6 +174,10 @@ fn run_watch(
                 return Err(Error::from(e).context("Terminal event listener failed"));
             }
         }
+
+        if let ExercisesProgress::AllDone = advance_while_done(&mut watch_state, &mut stdout)? {
+            return Ok(all_done(notify));
+        }
     }
 
     Ok(WatchExit::Shutdown)
@@ -136,9 +186,22 @@ fn run_watch(
 fn watch_list_loop(
     app_state: &mut AppState,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    auto_advance: bool,
+    show_upcoming: bool,
+    notify: bool,
+    feedback: &Feedback,
+    debounce: Duration,
 ) -> Result<()> {
     loop {
-        match run_watch(app_state, notify_exercise_names)? {
+        match run_watch(
+            app_state,
+            notify_exercise_names,
+            auto_advance,
+            show_upcoming,
+            notify,
+            feedback,
+            debounce,
+        )? {
             WatchExit::Shutdown => break Ok(()),
             // It is much easier to exit the watch mode, launch the list mode and then restart
             // the watch mode instead of trying to pause the watch threads and correct the
@@ -149,22 +212,47 @@ fn watch_list_loop(
 }
 
 /// `notify_exercise_names` as None activates the manual run mode.
+/// `auto_advance` moves on to the next exercise once the current one is done without waiting for `n`.
+/// `show_upcoming` lists the next pending exercises once the current one is done.
+/// `notify` shows a desktop notification once all exercises are done.
+/// `feedback` signals the result of checking an exercise.
+/// `debounce` is how long to wait for further changes of an exercise file before rerunning it.
 pub fn watch(
     app_state: &mut AppState,
     notify_exercise_names: Option<&'static [&'static [u8]]>,
+    auto_advance: bool,
+    show_upcoming: bool,
+    notify: bool,
+    feedback: &Feedback,
+    debounce: Duration,
 ) -> Result<()> {
     #[cfg(not(windows))]
     {
         let stdin_fd = rustix::stdio::stdin();
         let mut termios = rustix::termios::tcgetattr(stdin_fd)?;
         let original_local_modes = termios.local_modes;
+        // Restored by the Ctrl-C handler if the watch mode is interrupted.
+        if let Ok(mut watch_termios) = interrupt::WATCH_TERMIOS.lock() {
+            *watch_termios = Some(termios.clone());
+        }
         // Disable stdin line buffering and hide input.
         termios.local_modes -=
             rustix::termios::LocalModes::ICANON | rustix::termios::LocalModes::ECHO;
         rustix::termios::tcsetattr(stdin_fd, rustix::termios::OptionalActions::Now, &termios)?;
 
-        let res = watch_list_loop(app_state, notify_exercise_names);
+        let res = watch_list_loop(
+            app_state,
+            notify_exercise_names,
+            auto_advance,
+            show_upcoming,
+            notify,
+            feedback,
+            debounce,
+        );
 
+        if let Ok(mut watch_termios) = interrupt::WATCH_TERMIOS.lock() {
+            *watch_termios = None;
+        }
         termios.local_modes = original_local_modes;
         rustix::termios::tcsetattr(stdin_fd, rustix::termios::OptionalActions::Now, &termios)?;
 
@@ -172,7 +260,15 @@ pub fn watch(
     }
 
     #[cfg(windows)]
-    watch_list_loop(app_state, notify_exercise_names)
+    watch_list_loop(
+        app_state,
+        notify_exercise_names,
+        auto_advance,
+        show_upcoming,
+        notify,
+        feedback,
+        debounce,
+    )
 }
 
 const QUIT_MSG: &[u8] = b"
@@ -0,0 +1,53 @@
+use std::process::{Command, Stdio};
+
+const TITLE: &str = "Rustlings";
+const ALL_DONE_MSG: &str = "All exercises are done!";
+
+#[cfg(target_os = "macos")]
+fn notification_cmd() -> Command {
+    let mut cmd = Command::new("osascript");
+    cmd.arg("-e").arg(format!(
+        "display notification \"{ALL_DONE_MSG}\" with title \"{TITLE}\"",
+    ));
+    cmd
+}
+
+#[cfg(windows)]
+fn notification_cmd() -> Command {
+    let mut cmd = Command::new("powershell");
+    cmd.arg("-NoProfile").arg("-Command").arg(format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $n = New-Object System.Windows.Forms.NotifyIcon; \
+         $n.Icon = [System.Drawing.SystemIcons]::Information; \
+         $n.Visible = $true; \
+         $n.ShowBalloonTip(5000, '{TITLE}', '{ALL_DONE_MSG}', 'Info'); \
+         Start-Sleep -Seconds 5; \
+         $n.Dispose()",
+    ));
+    cmd
+}
+
+// Linux and BSDs with a notification daemon.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn notification_cmd() -> Command {
+    let mut cmd = Command::new("notify-send");
+    cmd.arg(TITLE).arg(ALL_DONE_MSG);
+    cmd
+}
+
+/// Show a desktop notification that all exercises are done.
+/// It is best-effort: Failing to show it is only logged.
+pub fn notify_all_done() {
+    let mut cmd = notification_cmd();
+    log::debug!("Running the command to show a desktop notification: {cmd:?}");
+
+    // Don't wait for the command because it could take a while (e.g. on Windows).
+    if let Err(e) = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        log::warn!("Failed to show a desktop notification: {e}");
+    }
+}
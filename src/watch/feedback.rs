@@ -0,0 +1,105 @@
+use std::{
+    io::{self, StdoutLock, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+const BELL: &[u8] = b"\x07";
+// Two bells right after each other are often merged into one.
+const FAILURE_BELL_PAUSE: Duration = Duration::from_millis(200);
+
+/// Optional signals after an exercise was checked in the watch mode.
+/// All of them are disabled by default.
+#[derive(Default)]
+pub struct Feedback {
+    /// Ring the terminal bell once if the exercise is done and twice if it is pending.
+    pub bell: bool,
+    /// Play this sound file if the exercise is done.
+    pub sound: Option<PathBuf>,
+}
+
+impl Feedback {
+    pub(super) fn exercise_checked(&self, stdout: &mut StdoutLock, done: bool) -> io::Result<()> {
+        if self.bell {
+            stdout.write_all(BELL)?;
+            if !done {
+                stdout.flush()?;
+                thread::sleep(FAILURE_BELL_PAUSE);
+                stdout.write_all(BELL)?;
+            }
+            stdout.flush()?;
+        }
+
+        if done {
+            if let Some(sound) = &self.sound {
+                play_sound(sound_cmd(sound));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn sound_cmd(sound: &Path) -> Command {
+    let mut cmd = Command::new("afplay");
+    cmd.arg(sound);
+    cmd
+}
+
+#[cfg(windows)]
+fn sound_cmd(sound: &Path) -> Command {
+    // Quotes in a single-quoted PowerShell string are escaped by doubling them.
+    // PowerShell also treats the typographic single quotes as single quotes.
+    let mut escaped_path = String::new();
+    for c in sound.display().to_string().chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            escaped_path.push(c);
+        }
+        escaped_path.push(c);
+    }
+
+    let mut cmd = Command::new("powershell");
+    cmd.arg("-NoProfile").arg("-Command").arg(format!(
+        "(New-Object Media.SoundPlayer '{escaped_path}').PlaySync()",
+    ));
+    cmd
+}
+
+// Linux and BSDs with PulseAudio or PipeWire.
+#[cfg(not(any(target_os = "macos", windows)))]
+fn sound_cmd(sound: &Path) -> Command {
+    let mut cmd = Command::new("paplay");
+    cmd.arg(sound);
+    cmd
+}
+
+// It is best-effort: Failing to play the sound is only logged.
+fn play_sound(mut cmd: Command) {
+    log::debug!("Running the command to play a sound: {cmd:?}");
+
+    let mut child = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::warn!("Failed to play the sound: {e}");
+            return;
+        }
+    };
+
+    // Don't wait for the sound to finish, but wait for the child process in the background
+    // to not leave a zombie process behind.
+    if let Err(e) = thread::Builder::new().spawn(move || {
+        if let Err(e) = child.wait() {
+            log::warn!("Failed to wait for the sound player: {e}");
+        }
+    }) {
+        log::warn!("Failed to spawn a thread to wait for the sound player: {e}");
+    }
+}
@@ -14,7 +14,37 @@ use std::{
 
 use super::{EXERCISE_RUNNING, WatchEvent};
 
-const DEBOUNCE_DURATION: Duration = Duration::from_millis(200);
+/// Whether the event could be the result of writing to a file.
+pub fn is_write_event(kind: EventKind) -> bool {
+    match kind {
+        EventKind::Any => true,
+        EventKind::Modify(modify_kind) => match modify_kind {
+            ModifyKind::Any | ModifyKind::Data(_) => true,
+            ModifyKind::Name(rename_mode) => match rename_mode {
+                RenameMode::Any | RenameMode::To => true,
+                RenameMode::From | RenameMode::Both | RenameMode::Other => false,
+            },
+            ModifyKind::Metadata(metadata_kind) => match metadata_kind {
+                MetadataKind::Any | MetadataKind::WriteTime => true,
+                MetadataKind::AccessTime
+                | MetadataKind::Permissions
+                | MetadataKind::Ownership
+                | MetadataKind::Extended
+                | MetadataKind::Other => false,
+            },
+            ModifyKind::Other => false,
+        },
+        EventKind::Access(access_kind) => match access_kind {
+            AccessKind::Any => true,
+            AccessKind::Close(access_mode) => match access_mode {
+                AccessMode::Any | AccessMode::Write => true,
+                AccessMode::Execute | AccessMode::Read | AccessMode::Other => false,
+            },
+            AccessKind::Read | AccessKind::Open(_) | AccessKind::Other => false,
+        },
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Other => false,
+    }
+}
 
 pub struct NotifyEventHandler {
     error_sender: Sender<WatchEvent>,
@@ -25,9 +55,11 @@ pub struct NotifyEventHandler {
 }
 
 impl NotifyEventHandler {
+    /// A file change is only reported after no further changes happened during `debounce`.
     pub fn build(
         watch_event_sender: Sender<WatchEvent>,
         exercise_names: &'static [&'static [u8]],
+        debounce: Duration,
     ) -> Result<Self> {
         let (update_sender, update_receiver) = sync_channel(0);
         let error_sender = watch_event_sender.clone();
@@ -38,7 +70,7 @@ impl NotifyEventHandler {
                 let mut exercise_updated = vec![false; exercise_names.len()];
 
                 loop {
-                    match update_receiver.recv_timeout(DEBOUNCE_DURATION) {
+                    match update_receiver.recv_timeout(debounce) {
                         Ok(exercise_ind) => exercise_updated[exercise_ind] = true,
                         Err(RecvTimeoutError::Timeout) => {
                             for (exercise_ind, updated) in exercise_updated.iter_mut().enumerate() {
@@ -84,33 +116,8 @@ impl notify::EventHandler for NotifyEventHandler {
             }
         };
 
-        match input_event.kind {
-            EventKind::Any => (),
-            EventKind::Modify(modify_kind) => match modify_kind {
-                ModifyKind::Any | ModifyKind::Data(_) => (),
-                ModifyKind::Name(rename_mode) => match rename_mode {
-                    RenameMode::Any | RenameMode::To => (),
-                    RenameMode::From | RenameMode::Both | RenameMode::Other => return,
-                },
-                ModifyKind::Metadata(metadata_kind) => match metadata_kind {
-                    MetadataKind::Any | MetadataKind::WriteTime => (),
-                    MetadataKind::AccessTime
-                    | MetadataKind::Permissions
-                    | MetadataKind::Ownership
-                    | MetadataKind::Extended
-                    | MetadataKind::Other => return,
-                },
-                ModifyKind::Other => return,
-            },
-            EventKind::Access(access_kind) => match access_kind {
-                AccessKind::Any => (),
-                AccessKind::Close(access_mode) => match access_mode {
-                    AccessMode::Any | AccessMode::Write => (),
-                    AccessMode::Execute | AccessMode::Read | AccessMode::Other => return,
-                },
-                AccessKind::Read | AccessKind::Open(_) | AccessKind::Other => return,
-            },
-            EventKind::Create(_) | EventKind::Remove(_) | EventKind::Other => return,
+        if !is_write_event(input_event.kind) {
+            return;
         }
 
         let _ = input_event
@@ -123,9 +130,17 @@ impl notify::EventHandler for NotifyEventHandler {
                     return None;
                 };
 
-                self.exercise_names
-                    .iter()
-                    .position(|exercise_name| *exercise_name == file_name_without_ext)
+                let position = |name: &[u8]| {
+                    self.exercise_names
+                        .iter()
+                        .position(|exercise_name| *exercise_name == name)
+                };
+
+                // The files of a multi-file exercise are in the directory with its name.
+                position(file_name_without_ext).or_else(|| {
+                    let dir_name = path.parent()?.file_name()?.to_str()?.as_bytes();
+                    position(dir_name)
+                })
             })
             .try_for_each(|exercise_ind| self.update_sender.send(exercise_ind));
     }
@@ -0,0 +1,117 @@
+use anyhow::{Context, Error, Result, bail};
+use crossterm::style::{Color, ResetColor, SetForegroundColor};
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    io::{self, StdoutLock, Write},
+    path::Path,
+    sync::mpsc::{RecvTimeoutError, channel},
+    time::Duration,
+};
+
+use crate::{
+    cmd::CmdRunner,
+    exercise::OUTPUT_CAPACITY,
+    term::{QueueStyle, clear_terminal, terminal_file_link},
+};
+
+use super::notify_event::is_write_event;
+
+fn run_file(
+    stdout: &mut StdoutLock,
+    cmd_runner: &CmdRunner,
+    path: &str,
+    canonical_path: &Path,
+    output: &mut Vec<u8>,
+) -> Result<()> {
+    output.clear();
+    let success = cmd_runner.compile_and_run_file(canonical_path, output)?;
+
+    clear_terminal(stdout)?;
+    stdout.write_all(output)?;
+
+    if success {
+        stdout.queue_style(SetForegroundColor(Color::Green))?;
+        stdout.write_all(b"Successfully ran ")?;
+    } else {
+        stdout.queue_style(SetForegroundColor(Color::Red))?;
+        stdout.write_all(b"Ran ")?;
+    }
+    stdout.queue_style(ResetColor)?;
+    terminal_file_link(stdout, path, &canonical_path.to_string_lossy(), Color::Blue)?;
+    if !success {
+        stdout.write_all(b" with errors")?;
+    }
+
+    stdout.write_all(b"\n\nWaiting for changes. Press Ctrl-C to exit\n")?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Compile and run a standalone Rust file every time it is saved.
+/// It is treated like an exercise which is only compiled and run, but it is never marked as done.
+/// Only returns on errors. Ctrl-C exits the program.
+pub fn watch_scratch_file(cmd_runner: &CmdRunner, path: &Path, debounce: Duration) -> Result<()> {
+    if path.extension().is_none_or(|ext| ext != "rs") || !path.is_file() {
+        bail!("The path {} is not a Rust file (`.rs`)", path.display());
+    }
+
+    // Compare canonical paths because the events contain absolute paths.
+    let canonical_path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to get the absolute path of {}", path.display()))?;
+    // A canonical path of a file always has a parent.
+    let dir = canonical_path
+        .parent()
+        .context("The file has no parent directory")?;
+
+    let (event_sender, event_receiver) = channel();
+    let watched_path = canonical_path.clone();
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    let _ = event_sender.send(Err(e));
+                    return;
+                }
+            };
+
+            if is_write_event(event.kind) && event.paths.contains(&watched_path) {
+                let _ = event_sender.send(Ok(()));
+            }
+        },
+        Config::default()
+            .with_follow_symlinks(false)
+            .with_poll_interval(Duration::from_secs(1)),
+    )
+    .context(NOTIFY_ERR)?;
+
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .context(NOTIFY_ERR)?;
+
+    let mut stdout = io::stdout().lock();
+    let mut output = Vec::with_capacity(OUTPUT_CAPACITY);
+    let path = path.to_string_lossy();
+
+    loop {
+        run_file(&mut stdout, cmd_runner, &path, &canonical_path, &mut output)?;
+
+        // Wait for the next change and skip the events of the same save.
+        let mut event = event_receiver.recv().context(NOTIFY_ERR)?;
+        loop {
+            event.map_err(|e| Error::from(e).context(NOTIFY_ERR))?;
+
+            event = match event_receiver.recv_timeout(debounce) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    bail!(NOTIFY_ERR);
+                }
+            };
+        }
+    }
+}
+
+const NOTIFY_ERR: &str = "The automatic detection of changes of the file failed";
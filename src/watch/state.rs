@@ -1,6 +1,5 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use crossterm::{
-    QueueableCommand,
     style::{
         Attribute, Attributes, Color, ResetColor, SetAttribute, SetAttributes, SetForegroundColor,
     },
@@ -8,23 +7,74 @@ use crossterm::{
 };
 use std::{
     io::{self, Read, StdoutLock, Write},
-    sync::mpsc::{Sender, SyncSender, sync_channel},
+    sync::mpsc::{RecvTimeoutError, Sender, SyncSender, sync_channel},
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
     app_state::{AppState, ExercisesProgress},
-    clear_terminal,
-    exercise::{OUTPUT_CAPACITY, RunnableExercise, solution_link_line},
-    term::progress_bar,
+    cmd::CmdRunner,
+    exercise::{Exercise, OUTPUT_CAPACITY, PendingReason, RunnableExercise, solution_link_line},
+    term::{self, QueueStyle, clear_terminal, page, progress_bar},
 };
 
-use super::{InputPauseGuard, WatchEvent, terminal_event::terminal_event_handler};
+use super::{Feedback, InputPauseGuard, WatchEvent, terminal_event::terminal_event_handler};
 
 const HEADING_ATTRIBUTES: Attributes = Attributes::none()
     .with(Attribute::Bold)
     .with(Attribute::Underlined);
 
+const N_UPCOMING_EXERCISES: usize = 3;
+// How often the elapsed time is updated while checking an exercise.
+const ELAPSED_TIME_TICK: Duration = Duration::from_secs(1);
+
+// Check the exercise in another thread to show the elapsed seconds in the waiting message.
+// This shows that the check isn't hanging on slow compilations.
+fn check_with_elapsed_time(
+    stdout: &mut StdoutLock,
+    exercise: &Exercise,
+    output: &mut Vec<u8>,
+    cmd_runner: &CmdRunner,
+) -> Result<Option<PendingReason>> {
+    let message = format!("Checking the exercise `{}`. Please wait…", exercise.name);
+    stdout.write_all(b"\n")?;
+    stdout.write_all(message.as_bytes())?;
+    stdout.flush()?;
+
+    let dumb_terminal = term::dumb_terminal();
+    let start = Instant::now();
+    thread::scope(|s| {
+        let (result_sender, result_receiver) = sync_channel(1);
+        thread::Builder::new()
+            .spawn_scoped(s, move || {
+                // Only fails if the receiver was dropped because of an error.
+                let _ =
+                    result_sender.send(exercise.exercise_pending_reason(Some(output), cmd_runner));
+            })
+            .context("Failed to spawn a thread to check the exercise")?;
+
+        loop {
+            match result_receiver.recv_timeout(ELAPSED_TIME_TICK) {
+                Ok(result) => break result,
+                Err(RecvTimeoutError::Timeout) => {
+                    // A dumb terminal might not support returning to the start of the line.
+                    let line_start = if dumb_terminal { "\n" } else { "\r" };
+                    write!(
+                        stdout,
+                        "{line_start}{message} ({}s)",
+                        start.elapsed().as_secs(),
+                    )?;
+                    stdout.flush()?;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    bail!("The thread checking the exercise stopped without a result")
+                }
+            }
+        }
+    })
+}
+
 #[derive(PartialEq, Eq)]
 enum DoneStatus {
     DoneWithSolution(String),
@@ -32,12 +82,41 @@ enum DoneStatus {
     Pending,
 }
 
+fn pending_guidance(pending_reason: PendingReason) -> &'static str {
+    match pending_reason {
+        PendingReason::CompilationFailed => {
+            "The exercise doesn't compile yet. Fix the errors above"
+        }
+        PendingReason::TestsFailed => "The exercise compiles, but some tests fail",
+        PendingReason::ClippyFailed => "The exercise works, but Clippy has some suggestions",
+        PendingReason::RunFailed => "The exercise compiles, but it didn't run successfully",
+        PendingReason::OutputMismatch => {
+            "The exercise runs, but its output doesn't match the expected output"
+        }
+        PendingReason::TooSlow => "The exercise works, but it isn't fast enough yet",
+        PendingReason::StyleViolations => {
+            "The exercise contains tabs or trailing whitespace. Fix them before compiling"
+        }
+        PendingReason::ForbiddenPattern => {
+            "The exercise contains a forbidden pattern. Solve it without the pattern above"
+        }
+        PendingReason::HintNotViewed => {
+            "The exercise works, but the teach mode requires viewing its hint first. Enter `h`"
+        }
+    }
+}
+
 pub struct WatchState<'a> {
     app_state: &'a mut AppState,
     output: Vec<u8>,
     show_hint: bool,
     done_status: DoneStatus,
+    // Why the current exercise is pending to show matching guidance.
+    pending_reason: Option<PendingReason>,
     manual_run: bool,
+    auto_advance: bool,
+    show_upcoming: bool,
+    feedback: &'a Feedback,
     term_width: u16,
     terminal_event_unpause_sender: SyncSender<()>,
 }
@@ -47,6 +126,9 @@ impl<'a> WatchState<'a> {
         app_state: &'a mut AppState,
         watch_event_sender: Sender<WatchEvent>,
         manual_run: bool,
+        auto_advance: bool,
+        show_upcoming: bool,
+        feedback: &'a Feedback,
     ) -> Result<Self> {
         let term_width = terminal::size()
             .context("Failed to get the terminal size")?
@@ -69,7 +151,11 @@ impl<'a> WatchState<'a> {
             output: Vec::with_capacity(OUTPUT_CAPACITY),
             show_hint: false,
             done_status: DoneStatus::Pending,
+            pending_reason: None,
             manual_run,
+            auto_advance,
+            show_upcoming,
+            feedback,
             term_width,
             terminal_event_unpause_sender,
         })
@@ -81,24 +167,35 @@ impl<'a> WatchState<'a> {
 
         self.show_hint = false;
 
-        writeln!(
-            stdout,
-            "\nChecking the exercise `{}`. Please wait…",
-            self.app_state.current_exercise().name,
-        )?;
-
-        let success = self
-            .app_state
-            .current_exercise()
-            .run_exercise(Some(&mut self.output), self.app_state.cmd_runner())?;
+        let exercise = self.app_state.current_exercise();
+        let success = if exercise.missing() {
+            self.output.clear();
+            write!(
+                self.output,
+                "The exercise file {} is missing.\nEnter `x` to restore it\n",
+                exercise.path,
+            )?;
+            self.pending_reason = None;
+            false
+        } else {
+            self.pending_reason = check_with_elapsed_time(
+                stdout,
+                exercise,
+                &mut self.output,
+                self.app_state.cmd_runner(),
+            )?;
+            if self.pending_reason.is_none()
+                && self
+                    .app_state
+                    .hint_required(self.app_state.current_exercise_ind())
+            {
+                self.pending_reason = Some(PendingReason::HintNotViewed);
+            }
+            self.pending_reason.is_none()
+        };
         self.output.push(b'\n');
         if success {
-            self.done_status =
-                if let Some(solution_path) = self.app_state.current_solution_path()? {
-                    DoneStatus::DoneWithSolution(solution_path)
-                } else {
-                    DoneStatus::DoneWithoutSolution
-                };
+            self.set_done_status()?;
         } else {
             self.app_state
                 .set_pending(self.app_state.current_exercise_ind())?;
@@ -107,6 +204,17 @@ impl<'a> WatchState<'a> {
         }
 
         self.render(stdout)?;
+        self.feedback.exercise_checked(stdout, success)?;
+        Ok(())
+    }
+
+    fn set_done_status(&mut self) -> Result<()> {
+        self.done_status = if let Some(solution_path) = self.app_state.current_solution_path()? {
+            DoneStatus::DoneWithSolution(solution_path)
+        } else {
+            DoneStatus::DoneWithoutSolution
+        };
+
         Ok(())
     }
 
@@ -148,6 +256,17 @@ impl<'a> WatchState<'a> {
         Ok(())
     }
 
+    /// Scroll through the output of the last run in the pager.
+    pub fn page_output(&mut self, stdout: &mut StdoutLock) -> Result<()> {
+        if page(stdout, &self.output)? {
+            self.render(stdout)?;
+        }
+
+        self.terminal_event_unpause_sender.send(())?;
+
+        Ok(())
+    }
+
     pub fn handle_file_change(
         &mut self,
         exercise_ind: usize,
@@ -170,22 +289,28 @@ impl<'a> WatchState<'a> {
         self.app_state.done_current_exercise::<true>(stdout)
     }
 
+    /// The current exercise is done and the user wants to move on automatically.
+    #[inline]
+    pub fn should_auto_advance(&self) -> bool {
+        self.auto_advance && self.done_status != DoneStatus::Pending
+    }
+
     fn show_prompt(&self, stdout: &mut StdoutLock) -> io::Result<()> {
         if self.done_status != DoneStatus::Pending {
-            stdout.queue(SetAttribute(Attribute::Bold))?;
+            stdout.queue_style(SetAttribute(Attribute::Bold))?;
             stdout.write_all(b"n")?;
-            stdout.queue(ResetColor)?;
+            stdout.queue_style(ResetColor)?;
             stdout.write_all(b":")?;
-            stdout.queue(SetAttribute(Attribute::Underlined))?;
+            stdout.queue_style(SetAttribute(Attribute::Underlined))?;
             stdout.write_all(b"next")?;
-            stdout.queue(ResetColor)?;
+            stdout.queue_style(ResetColor)?;
             stdout.write_all(b" / ")?;
         }
 
         let mut show_key = |key, postfix| {
-            stdout.queue(SetAttribute(Attribute::Bold))?;
+            stdout.queue_style(SetAttribute(Attribute::Bold))?;
             stdout.write_all(&[key])?;
-            stdout.queue(ResetColor)?;
+            stdout.queue_style(ResetColor)?;
             stdout.write_all(postfix)
         };
 
@@ -199,12 +324,29 @@ impl<'a> WatchState<'a> {
 
         show_key(b'l', b":list / ")?;
         show_key(b'c', b":check all / ")?;
+        show_key(b'o', b":output / ")?;
         show_key(b'x', b":reset / ")?;
         show_key(b'q', b":quit ? ")?;
 
         stdout.flush()
     }
 
+    // Nothing is written after the last pending exercise.
+    fn write_upcoming_exercises(&self, stdout: &mut StdoutLock) -> io::Result<()> {
+        let mut upcoming_exercises = self.app_state.upcoming_exercises(N_UPCOMING_EXERCISES);
+        let Some(first) = upcoming_exercises.next() else {
+            return Ok(());
+        };
+
+        stdout.write_all(b"Next up: ")?;
+        stdout.write_all(first.name.as_bytes())?;
+        for exercise in upcoming_exercises {
+            stdout.write_all(b", ")?;
+            stdout.write_all(exercise.name.as_bytes())?;
+        }
+        stdout.write_all(b"\n")
+    }
+
     pub fn render(&self, stdout: &mut StdoutLock) -> io::Result<()> {
         // Prevent having the first line shifted if clearing wasn't successful.
         stdout.write_all(b"\n")?;
@@ -214,22 +356,31 @@ impl<'a> WatchState<'a> {
 
         if self.show_hint {
             stdout
-                .queue(SetAttributes(HEADING_ATTRIBUTES))?
-                .queue(SetForegroundColor(Color::Cyan))?;
+                .queue_style(SetAttributes(HEADING_ATTRIBUTES))?
+                .queue_style(SetForegroundColor(Color::Cyan))?;
             stdout.write_all(b"Hint")?;
-            stdout.queue(ResetColor)?;
+            stdout.queue_style(ResetColor)?;
             stdout.write_all(b"\n")?;
 
             stdout.write_all(self.app_state.current_exercise().hint.as_bytes())?;
             stdout.write_all(b"\n\n")?;
         }
 
+        if let (DoneStatus::Pending, Some(pending_reason)) =
+            (&self.done_status, self.pending_reason)
+        {
+            stdout.queue_style(SetForegroundColor(Color::Yellow))?;
+            stdout.write_all(pending_guidance(pending_reason).as_bytes())?;
+            stdout.queue_style(ResetColor)?;
+            stdout.write_all(b"\n\n")?;
+        }
+
         if self.done_status != DoneStatus::Pending {
             stdout
-                .queue(SetAttribute(Attribute::Bold))?
-                .queue(SetForegroundColor(Color::Green))?;
+                .queue_style(SetAttribute(Attribute::Bold))?
+                .queue_style(SetForegroundColor(Color::Green))?;
             stdout.write_all("Exercise done ✓".as_bytes())?;
-            stdout.queue(ResetColor)?;
+            stdout.queue_style(ResetColor)?;
             stdout.write_all(b"\n")?;
 
             if let DoneStatus::DoneWithSolution(solution_path) = &self.done_status {
@@ -237,9 +388,14 @@ impl<'a> WatchState<'a> {
             }
 
             stdout.write_all(
-                "When done experimenting, enter `n` to move on to the next exercise 🦀\n\n"
+                "When done experimenting, enter `n` to move on to the next exercise 🦀\n"
                     .as_bytes(),
             )?;
+
+            if self.show_upcoming {
+                self.write_upcoming_exercises(stdout)?;
+            }
+            stdout.write_all(b"\n")?;
         }
 
         progress_bar(
@@ -260,9 +416,16 @@ impl<'a> WatchState<'a> {
         Ok(())
     }
 
-    pub fn show_hint(&mut self, stdout: &mut StdoutLock) -> io::Result<()> {
+    pub fn show_hint(&mut self, stdout: &mut StdoutLock) -> Result<()> {
         if !self.show_hint {
             self.show_hint = true;
+            self.app_state
+                .mark_hint_viewed(self.app_state.current_exercise_ind())?;
+            // The exercise was only blocked by the teach mode.
+            if self.pending_reason == Some(PendingReason::HintNotViewed) {
+                self.pending_reason = None;
+                self.set_done_status()?;
+            }
             self.render(stdout)?;
         }
 
@@ -273,7 +436,9 @@ impl<'a> WatchState<'a> {
         // Ignore any input until checking all exercises is done.
         let _input_pause_guard = InputPauseGuard::scoped_pause();
 
-        if let Some(first_pending_exercise_ind) = self.app_state.check_all_exercises(stdout)? {
+        if let Some(first_pending_exercise_ind) =
+            self.app_state.check_all_exercises(stdout, false, None)?
+        {
             // Only change exercise if the current one is done.
             if self.app_state.current_exercise().done {
                 self.app_state
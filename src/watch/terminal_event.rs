@@ -13,6 +13,7 @@ pub enum InputEvent {
     List,
     CheckAll,
     Reset,
+    Output,
     Quit,
 }
 
@@ -39,12 +40,17 @@ pub fn terminal_event_handler(
                     KeyCode::Char('h') => InputEvent::Hint,
                     KeyCode::Char('l') => break WatchEvent::Input(InputEvent::List),
                     KeyCode::Char('c') => InputEvent::CheckAll,
-                    KeyCode::Char('x') => {
-                        if sender.send(WatchEvent::Input(InputEvent::Reset)).is_err() {
+                    // Pause input until quitting the confirmation prompt or the pager.
+                    KeyCode::Char(c @ ('x' | 'o')) => {
+                        let input_event = if c == 'x' {
+                            InputEvent::Reset
+                        } else {
+                            InputEvent::Output
+                        };
+                        if sender.send(WatchEvent::Input(input_event)).is_err() {
                             return;
                         }
 
-                        // Pause input until quitting the confirmation prompt.
                         if unpause_receiver.recv().is_err() {
                             return;
                         };
@@ -8,6 +8,8 @@ enum Output<'a> {
     FullStdout(&'a str),
     PartialStdout(&'a str),
     PartialStderr(&'a str),
+    /// Stdout without any escape sequences.
+    PlainStdout,
 }
 
 use Output::*;
@@ -81,8 +83,18 @@ impl<'a> Cmd<'a> {
                 assert!(from_utf8(&output.stderr).unwrap().contains(stderr));
                 output.status
             }
+            Some(PlainStdout) => {
+                let output = cmd.stderr(Stdio::null()).output().unwrap();
+                assert!(!output.stdout.contains(&b'\x1b'));
+                output.status
+            }
         };
 
+        // Don't leave the history of done exercises behind.
+        if let Some(current_dir) = self.current_dir {
+            let _ = std::fs::remove_file(format!("{current_dir}/.rustlings-history.jsonl"));
+        }
+
         assert_eq!(status.success(), success, "{cmd:?}");
     }
 
@@ -105,11 +117,32 @@ fn run_compilation_success() {
         .success();
 }
 
+#[test]
+fn run_multi_file_success() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "multi_file_success"])
+        .output(PartialStdout("Hello from a module"))
+        .success();
+}
+
+#[test]
+fn run_quiet() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "--quiet", "compilation_success"])
+        .output(FullStdout("OK compilation_success\n"))
+        .success();
+}
+
 #[test]
 fn run_compilation_failure() {
     Cmd::default()
         .current_dir("tests/test_exercises")
         .args(&["run", "compilation_failure"])
+        .output(PartialStdout(
+            "First error in exercises/compilation_failure.rs\n  1 | fn main() {\n  2 |     let\n> 3 | }\n",
+        ))
         .fail();
 }
 
@@ -130,6 +163,173 @@ fn run_test_failure() {
         .fail();
 }
 
+#[test]
+fn run_test_filter() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "test_filter"])
+        .output(PartialStdout("Successfully ran"))
+        .success();
+}
+
+#[test]
+fn run_exit_code_success() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "exit_code_success"])
+        .output(PartialStdout("Exiting with the code 2"))
+        .success();
+}
+
+#[test]
+fn run_expected_output_success() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "expected_output_success"])
+        .output(PartialStdout("Successfully ran"))
+        .success();
+}
+
+#[test]
+fn run_env_success() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "env_success"])
+        .output(PartialStdout("Hello from the environment"))
+        .success();
+}
+
+#[test]
+fn run_input() {
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let input_path = format!("{}/input.txt", test_dir.path().to_str().unwrap());
+    std::fs::write(&input_path, "Hello from stdin\n").unwrap();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "stdin_success", "--input", &input_path])
+        .output(PartialStdout("Read: Hello from stdin"))
+        .success();
+}
+
+#[test]
+fn run_expected_output_failure() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "expected_output_failure"])
+        .output(PartialStdout("- world\n+ Rust\n"))
+        .fail();
+}
+
+#[test]
+fn run_bench_success() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "bench_success"])
+        .output(PartialStdout("(maximum: 1000000000 ns)"))
+        .success();
+}
+
+#[test]
+fn run_bench_failure() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "bench_failure"])
+        .output(PartialStdout("Measured time: 2000 ns (maximum: 1000 ns)"))
+        .fail();
+}
+
+#[test]
+fn run_requires_failure() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "requires_failure"])
+        .output(PartialStderr(
+            "requires the following exercises to be done first: compilation_failure",
+        ))
+        .fail();
+}
+
+#[test]
+fn run_style_failure() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "style_failure"])
+        .output(PartialStdout(
+            "exercises/style_failure.rs:2: tab instead of spaces\n\
+             exercises/style_failure.rs:2: trailing whitespace\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn run_forbidden_failure() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "forbidden_failure"])
+        .output(PartialStdout(
+            "Forbidden pattern found\n\
+             exercises/forbidden_failure.rs:2: `println!(\"42\")`\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn run_keep_artifacts() {
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let artifacts_dir = test_dir.path().join("artifacts");
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&[
+            "run",
+            "test_success",
+            "--keep-artifacts",
+            artifacts_dir.to_str().unwrap(),
+        ])
+        .output(PartialStdout("Kept the binary"))
+        .success();
+
+    // The exercise binary and the test binary.
+    assert!(artifacts_dir.join("test_success").is_file());
+    assert_eq!(std::fs::read_dir(&artifacts_dir).unwrap().count(), 2);
+}
+
+#[test]
+fn run_piped_output_without_escape_sequences() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "test_success"])
+        .output(PlainStdout)
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "test_failure"])
+        .output(PlainStdout)
+        .fail();
+}
+
+#[test]
+fn run_log_level_debug() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["--log-level", "debug", "run", "compilation_success"])
+        .output(PartialStderr("[DEBUG] Running the command `cargo build …`"))
+        .success();
+}
+
+#[test]
+fn run_print_command() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["run", "test_failure", "--print-command"])
+        .output(PartialStdout(
+            "\"cargo\" \"test\" \"-q\" \"--bin\" \"test_failure\"",
+        ))
+        .success();
+}
+
 #[test]
 fn run_exercise_not_in_info() {
     Cmd::default()
@@ -138,11 +338,182 @@ fn run_exercise_not_in_info() {
         .fail();
 }
 
+#[test]
+fn check_all_show_failures() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--show-failures"])
+        .output(PartialStdout(
+            "The output doesn't match the expected output",
+        ))
+        .fail();
+}
+
+#[test]
+fn check_all_quiet() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--quiet"])
+        .output(PartialStdout(
+            "\nexpected_output_failure (exercises/expected_output_failure.rs)\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn check_all_then_watch() {
+    // The watch mode requires a terminal which is checked before checking any exercise.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--then-watch"])
+        .output(PartialStderr("Unsupported or missing terminal/TTY"))
+        .fail();
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--then-watch"])
+        .output(FullStdout(""))
+        .fail();
+}
+
+#[test]
+fn check_all_report() {
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let report_path = format!("{}/junit.xml", test_dir.path().to_str().unwrap());
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&[
+            "check-all",
+            "--quiet",
+            "--from",
+            "style_failure",
+            "--report",
+            &report_path,
+        ])
+        .fail();
+
+    let report = std::fs::read_to_string(&report_path).unwrap();
+    assert!(report.starts_with("<?xml"));
+    assert!(report.contains("tests=\"3\" failures=\"1\""));
+    assert!(report.contains("<testcase name=\"style_failure\" classname=\"exercises\""));
+    assert!(report.contains("exercises/style_failure.rs:2: tab instead of spaces\n"));
+}
+
+#[test]
+fn check_all_nothing_to_check() {
+    // No exercise after `style_failure` has the tag `success`.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--from", "style_failure", "--tag", "success"])
+        .output(FullStdout("No exercises to check\n"))
+        .success();
+}
+
+#[test]
+fn check_all_from() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--quiet", "--from", "style_failure"])
+        .output(FullStdout(
+            "\nstyle_failure (exercises/style_failure.rs)\n\
+             The style check failed\n\
+             exercises/style_failure.rs:2: tab instead of spaces\n\
+             exercises/style_failure.rs:2: trailing whitespace\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn check_all_max_failures() {
+    // The first pending exercise is always checked.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--quiet", "--max-failures", "1"])
+        .output(PartialStdout(
+            "\ncompilation_failure (exercises/compilation_failure.rs)\n",
+        ))
+        .fail();
+}
+
+#[test]
+fn check_all_tag() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--quiet", "--tag", "success"])
+        .output(FullStdout(""))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["check-all", "--tag", "unknown"])
+        .output(PartialStderr("No exercises found with the tag `unknown`"))
+        .fail();
+}
+
+#[test]
+fn lint() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["lint", "test_success"])
+        .output(PartialStdout("test_success  0\n\nTotal: 0\n"))
+        .success();
+}
+
 #[test]
 fn reset_without_exercise_name() {
     Cmd::default().args(&["reset"]).fail();
 }
 
+#[test]
+fn reset_dry_run() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["reset", "test_success", "--dry-run"])
+        .output(PartialStdout(
+            "exercises/test_success.rs\n\
+             No changes compared to the original exercise file\n",
+        ))
+        .success();
+
+    // Like `reset` without a name.
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["reset", "--dry-run"])
+        .output(PartialStderr("required arguments were not provided"))
+        .fail();
+}
+
+#[test]
+fn reset_unknown_chapter() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["reset", "--chapter", "unknown"])
+        .output(PartialStderr("No exercises found in the chapter directory"))
+        .fail();
+}
+
+#[test]
+fn solution_missing() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["solution", "test_failure"])
+        .output(PartialStderr(
+            "The exercise test_failure has no solution file",
+        ))
+        .fail();
+}
+
+#[test]
+fn hint_all() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["hint", "--all"])
+        .output(PartialStdout(
+            "\ntest_failure (exercises/test_failure.rs)\nThe answer to everything: 42\n\n",
+        ))
+        .success();
+}
+
 #[test]
 fn hint() {
     Cmd::default()
@@ -152,6 +523,22 @@ fn hint() {
         .success();
 }
 
+#[test]
+fn search() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["search", "--hints-only", "EVERYTHING"])
+        .output(FullStdout(
+            "test_failure (exercises/test_failure.rs)\n  hint: The answer to everything: 42\n",
+        ))
+        .success();
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["search", "--source-only", "EVERYTHING"])
+        .fail();
+}
+
 #[test]
 fn init() {
     let test_dir = tempfile::TempDir::new().unwrap();
@@ -180,3 +567,85 @@ fn init() {
         .output(PartialStderr("already initialized"))
         .fail();
 }
+
+#[test]
+fn state_export() {
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let state_path = format!("{}/state.txt", test_dir.path().to_str().unwrap());
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["state", "export", &state_path])
+        .output(PartialStdout("has been exported"))
+        .success();
+
+    let state = std::fs::read_to_string(&state_path).unwrap();
+    assert!(state.starts_with("DON'T EDIT THIS FILE!\nversion: 4\n"));
+}
+
+#[test]
+fn stats() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["stats", "--format", "json"])
+        .output(PartialStdout("\"total\": 16"))
+        .success();
+}
+
+#[test]
+fn stats_oneline() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["stats", "--format", "oneline", "-n"])
+        .output(PartialStdout("/16 ("))
+        .success();
+}
+
+#[test]
+fn export() {
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let summary_path = format!("{}/summary.json", test_dir.path().to_str().unwrap());
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["export", "--format", "json", "--output", &summary_path])
+        .output(PartialStdout("has been exported"))
+        .success();
+
+    let summary = std::fs::read_to_string(&summary_path).unwrap();
+    assert!(summary.contains("\"total\": 16"));
+}
+
+#[test]
+fn badge() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["badge", "--format", "shields-json"])
+        // The message and the color depend on the progress in the shared state file.
+        .output(PartialStdout(
+            "{\n  \"schemaVersion\": 1,\n  \"label\": \"rustlings\",\n",
+        ))
+        .success();
+
+    let test_dir = tempfile::TempDir::new().unwrap();
+    let badge_path = format!("{}/progress.svg", test_dir.path().to_str().unwrap());
+
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["badge", "--output", &badge_path])
+        .success();
+
+    let badge = std::fs::read_to_string(&badge_path).unwrap();
+    assert!(badge.starts_with("<svg"));
+}
+
+#[test]
+fn graph() {
+    Cmd::default()
+        .current_dir("tests/test_exercises")
+        .args(&["graph"])
+        .output(PartialStdout(
+            "    \"compilation_failure\" -> \"requires_failure\";\n}\n",
+        ))
+        .success();
+}
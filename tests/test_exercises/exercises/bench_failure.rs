@@ -0,0 +1,3 @@
+fn main() {
+    println!("bench_ns: 2000");
+}
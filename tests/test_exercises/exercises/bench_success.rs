@@ -0,0 +1,11 @@
+use std::{hint::black_box, time::Instant};
+
+fn sum(n: u64) -> u64 {
+    (1..=n).sum()
+}
+
+fn main() {
+    let start = Instant::now();
+    black_box(sum(black_box(1000)));
+    println!("bench_ns: {}", start.elapsed().as_nanos());
+}
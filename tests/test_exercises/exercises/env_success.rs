@@ -0,0 +1,19 @@
+use std::env;
+
+fn greeting() -> String {
+    env::var("GREETING").unwrap()
+}
+
+fn main() {
+    println!("{}", greeting());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_env() {
+        assert_eq!(greeting(), "Hello from the environment");
+    }
+}
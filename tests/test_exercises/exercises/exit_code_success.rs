@@ -0,0 +1,4 @@
+fn main() {
+    println!("Exiting with the code 2");
+    std::process::exit(2);
+}
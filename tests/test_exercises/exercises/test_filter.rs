@@ -0,0 +1,12 @@
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn basic_passes() {}
+
+    #[test]
+    fn slow_fails() {
+        panic!("This test is filtered out");
+    }
+}